@@ -1,19 +1,95 @@
+use rusty_neat_interchange::genome::PrintableActivation;
 use serde::{Deserialize, Serialize};
 use std::f64;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Activation {
     Identity,
+    #[default]
     Sigmoid,
     Relu,
+    Tanh,
+    Gaussian,
+    Sin,
+    Abs,
+    Step,
+    Softplus,
+    Clamped,     // Identität, auf [-1, 1] beschränkt
+    ClampedRelu, // Relu, auf [0, 1] beschränkt
+    Cos,
+    Square,
 }
 
+// Typische Aktivierungsfunktionen eines CPPN (siehe hyperneat), die symmetrische, periodische und
+// monotone Muster über die Koordinaten erzeugen
+pub const CPPN_ACTIVATIONS: [Activation; 9] = [
+    Activation::Identity,
+    Activation::Sigmoid,
+    Activation::Tanh,
+    Activation::Gaussian,
+    Activation::Sin,
+    Activation::Cos,
+    Activation::Abs,
+    Activation::Square,
+    Activation::Step,
+];
+
 impl Activation {
     pub fn function(&self) -> fn(f64) -> f64 {
         match self {
             Activation::Identity => identity,
             Activation::Sigmoid => sigmoid,
             Activation::Relu => relu,
+            Activation::Tanh => tanh,
+            Activation::Gaussian => gaussian,
+            Activation::Sin => sin,
+            Activation::Abs => abs,
+            Activation::Step => step,
+            Activation::Softplus => softplus,
+            Activation::Clamped => clamped,
+            Activation::ClampedRelu => clamped_relu,
+            Activation::Cos => cos,
+            Activation::Square => square,
+        }
+    }
+}
+
+impl From<&PrintableActivation> for Activation {
+    fn from(printable: &PrintableActivation) -> Self {
+        match printable {
+            PrintableActivation::Identity => Activation::Identity,
+            PrintableActivation::Sigmoid => Activation::Sigmoid,
+            PrintableActivation::Relu => Activation::Relu,
+            PrintableActivation::Tanh => Activation::Tanh,
+            PrintableActivation::Gaussian => Activation::Gaussian,
+            PrintableActivation::Sin => Activation::Sin,
+            PrintableActivation::Abs => Activation::Abs,
+            PrintableActivation::Step => Activation::Step,
+            PrintableActivation::Softplus => Activation::Softplus,
+            PrintableActivation::Clamped => Activation::Clamped,
+            PrintableActivation::ClampedRelu => Activation::ClampedRelu,
+            PrintableActivation::Cos => Activation::Cos,
+            PrintableActivation::Square => Activation::Square,
+        }
+    }
+}
+
+impl From<&Activation> for PrintableActivation {
+    fn from(activation: &Activation) -> Self {
+        match activation {
+            Activation::Identity => PrintableActivation::Identity,
+            Activation::Sigmoid => PrintableActivation::Sigmoid,
+            Activation::Relu => PrintableActivation::Relu,
+            Activation::Tanh => PrintableActivation::Tanh,
+            Activation::Gaussian => PrintableActivation::Gaussian,
+            Activation::Sin => PrintableActivation::Sin,
+            Activation::Abs => PrintableActivation::Abs,
+            Activation::Step => PrintableActivation::Step,
+            Activation::Softplus => PrintableActivation::Softplus,
+            Activation::Clamped => PrintableActivation::Clamped,
+            Activation::ClampedRelu => PrintableActivation::ClampedRelu,
+            Activation::Cos => PrintableActivation::Cos,
+            Activation::Square => PrintableActivation::Square,
         }
     }
 }
@@ -33,3 +109,52 @@ fn relu(x: f64) -> f64 {
         0.0
     }
 }
+
+fn tanh(x: f64) -> f64 {
+    x.tanh()
+}
+
+fn gaussian(x: f64) -> f64 {
+    (-x * x).exp()
+}
+
+fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+fn abs(x: f64) -> f64 {
+    x.abs()
+}
+
+fn step(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn softplus(x: f64) -> f64 {
+    // Für große x ist ln(1 + e^x) = x (vermeidet einen Überlauf von exp)
+    if x > 30.0 {
+        x
+    } else {
+        x.exp().ln_1p()
+    }
+}
+
+fn clamped(x: f64) -> f64 {
+    x.clamp(-1.0, 1.0)
+}
+
+fn clamped_relu(x: f64) -> f64 {
+    x.clamp(0.0, 1.0)
+}
+
+fn cos(x: f64) -> f64 {
+    x.cos()
+}
+
+fn square(x: f64) -> f64 {
+    x * x
+}
@@ -1,16 +1,19 @@
-use std::{
-    fmt::Display,
-    path::Path,
+use std::{fmt::Display, path::Path};
+
+use rusty_neat_core::{
+    coevolution::{Coevolution, CoevolutionConfig, Rating, Schedule},
+    config::ConfigLoader,
+    gene_pool::GenePool,
+    network::Network,
+    observer::StdoutObserver,
+    population::Population,
 };
-
-use rand::Rng;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use rusty_neat_core::{gene_pool::GenePool, organism::Organism, population::Population};
+use rusty_neat_interchange::io::FileType;
 
 const FIELD_WIDTH: usize = 7;
 const FIELD_HEIGHT: usize = 6;
 
-const GROUP_COUNT: usize = 4;
+const OPPONENT_COUNT: usize = 25;
 
 pub fn main() {
     neat();
@@ -18,51 +21,50 @@ pub fn main() {
 
 fn neat() {
     let pool = GenePool::new_dense((FIELD_WIDTH + 2) * (FIELD_HEIGHT + 2), FIELD_WIDTH);
-    let mut population = Population::new(pool, Path::new("connect_four.json")).unwrap();
-
-    let best_organism = population.evolve(
-        |organisms| {
-            for organism in organisms.iter_mut() {
-                organism.fitness = Some(0.0);
-            }
-
-            let groups = create_chunks(
-                organisms,
-                GROUP_COUNT,
-                organisms.len() / GROUP_COUNT,
-                |_| (&mut rand::thread_rng()).gen_range(0..GROUP_COUNT),
-            );
-
-            groups.par_iter().for_each(|group| {
-                println!("Evaluating group of size {}", group.len());
-                for i in 0..group.len() {
-                    for j in i + 1..group.len() {
-                        let result = play(group.get(i), group.get(j));
-                        match result {
-                            GameResult::FirstPlayerWon => {
-                                group.get(i).fitness = Some(group.get(i).fitness.unwrap() + 1.0);
-                            }
-                            GameResult::SecondPlayerWon => {
-                                group.get(j).fitness = Some(group.get(j).fitness.unwrap() + 1.0);
-                            }
-                            GameResult::Tie => {
-                                group.get(i).fitness = Some(group.get(i).fitness.unwrap() + 0.5);
-                                group.get(j).fitness = Some(group.get(j).fitness.unwrap() + 0.5);
-                            }
-                        }
-                    }
-                    group.get(i).fitness =
-                        Some(group.get(i).fitness.unwrap() / ((group.len() - 1) as f64));
-                }
-            });
+    // connect_four.json enthält nur die Abweichungen von config.json. Einzelne Werte können mit NEAT_*
+    // Umgebungsvariablen oder Argumenten der Form <pfad>=<wert> überschrieben werden.
+    let config = ConfigLoader::new()
+        .file(Path::new("config.json"))
+        .and_then(|loader| loader.file(Path::new("connect_four.json")))
+        .and_then(|loader| loader.env())
+        .and_then(|loader| loader.args(std::env::args().skip(1)))
+        .and_then(|loader| loader.load())
+        .unwrap();
+    let mut population = Population::with_config(pool, config).unwrap();
+    for warning in population.config_warnings() {
+        eprintln!("warning: {}", warning);
+    }
+    population.add_observer(StdoutObserver);
+    population
+        .write_generations(Path::new("connect_four_out"), FileType::Bincode)
+        .unwrap();
+
+    let mut coevolution = Coevolution::new(CoevolutionConfig {
+        schedule: Schedule::RandomOpponents {
+            count: OPPONENT_COUNT,
         },
-        Path::new("connect_four_out"),
-    );
+        rating: Rating::Score,
+    })
+    .unwrap();
+    let mut rng = rand::thread_rng();
+
+    let best_organism = population.evolve(|organisms| {
+        coevolution.evaluate(
+            organisms,
+            |first, second| match play(first.network(), second.network()) {
+                GameResult::FirstPlayerWon => (1.0, 0.0),
+                GameResult::SecondPlayerWon => (0.0, 1.0),
+                GameResult::Tie => (0.5, 0.5),
+            },
+            &mut rng,
+        )
+    });
 
     println!("==========================================");
     println!("{:?}", best_organism);
 }
 
+#[allow(dead_code)]
 fn interactive() {
     let mut board = Board::new();
 
@@ -80,7 +82,7 @@ fn interactive() {
                 break;
             }
 
-            board.next_player = -1.0 * board.next_player;
+            board.next_player = -board.next_player;
             current_turn += 1;
         }
     }
@@ -91,26 +93,23 @@ fn interactive() {
     }
 }
 
-fn play(first: &mut Organism, second: &mut Organism) -> GameResult {
+fn play(first: &Network, second: &Network) -> GameResult {
     let mut board = Board::new();
+    let mut output = [0.0; FIELD_WIDTH];
 
     let mut current_turn = 0;
     while current_turn < FIELD_WIDTH * FIELD_HEIGHT {
         let mut max_prob = 0.0;
         let mut max_index = 0;
         if board.next_player == 1.0 {
-            for (i, prob) in first.evaluate(&board.board).iter().enumerate() {
-                if *prob > max_prob {
-                    max_prob = *prob;
-                    max_index = i;
-                }
-            }
+            first.evaluate(&board.board, &mut output).unwrap();
         } else {
-            for (i, prob) in second.evaluate(&board.board).iter().enumerate() {
-                if *prob > max_prob {
-                    max_prob = *prob;
-                    max_index = i;
-                }
+            second.evaluate(&board.board, &mut output).unwrap();
+        }
+        for (i, prob) in output.iter().enumerate() {
+            if *prob > max_prob {
+                max_prob = *prob;
+                max_index = i;
             }
         }
 
@@ -120,7 +119,7 @@ fn play(first: &mut Organism, second: &mut Organism) -> GameResult {
                 break;
             }
 
-            board.next_player = -1.0 * board.next_player;
+            board.next_player = -board.next_player;
             current_turn += 1;
         } else {
             if board.next_player == 1.0 {
@@ -175,7 +174,7 @@ impl Board {
     pub fn check_win(&self, last_column: usize) -> bool {
         let height = self.heights[last_column] - 1;
 
-        let mut depth = 1 as usize;
+        let mut depth = 1_usize;
         let mut adjacent = 0;
 
         // Horizontal nach rechts
@@ -242,14 +241,12 @@ impl Board {
         }
 
         // Senkrecht nach unten
-        if height >= 4 {
-            if self.board[Self::index(last_column + 1, height - 1)] == self.next_player {
-                if self.board[Self::index(last_column + 1, height - 2)] == self.next_player {
-                    if self.board[Self::index(last_column + 1, height - 3)] == self.next_player {
-                        return true;
-                    }
-                }
-            }
+        if height >= 4
+            && self.board[Self::index(last_column + 1, height - 1)] == self.next_player
+            && self.board[Self::index(last_column + 1, height - 2)] == self.next_player
+            && self.board[Self::index(last_column + 1, height - 3)] == self.next_player
+        {
+            return true;
         }
 
         false
@@ -271,52 +268,8 @@ impl Display for Board {
                     write!(f, "{} ", player)?;
                 }
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
-
-///////////////////////////////////////////////////////////////////////////////////////////////////
-
-struct IndexedChunk<'a, T> {
-    values: &'a [T],
-    indices: Vec<usize>,
-}
-
-impl<'a, T> IndexedChunk<'a, T> {
-    pub fn get(&self, index: usize) -> &mut T {
-        let value = &self.values[self.indices[index]];
-        unsafe { (value as *const T as *mut T).as_mut().unwrap() }
-    }
-
-    pub fn len(&self) -> usize {
-        self.indices.len()
-    }
-}
-
-fn create_chunks<'a, T, F: Fn(&T) -> usize>(
-    values: &'a mut [T],
-    chunk_count: usize,
-    expected_chunk_size: usize,
-    assignment: F,
-) -> Vec<IndexedChunk<'a, T>> {
-    let values = &(*values);
-    let mut indices: Vec<Vec<usize>> = std::iter::repeat(Vec::with_capacity(expected_chunk_size))
-        .take(chunk_count)
-        .collect();
-
-    for (i, value) in values.iter().enumerate() {
-        indices[assignment(value)].push(i);
-    }
-
-    let mut chunks = Vec::with_capacity(chunk_count);
-    for index in indices {
-        chunks.push(IndexedChunk {
-            values,
-            indices: index,
-        });
-    }
-
-    chunks
-}
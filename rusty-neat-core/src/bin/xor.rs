@@ -1,47 +1,58 @@
-use rusty_neat_core::{gene_pool::GenePool, population::Population};
+use rusty_neat_core::{
+    config::ConfigLoader, gene_pool::GenePool, observer::StdoutObserver, population::Population,
+};
+use rusty_neat_interchange::io::FileType;
 
 use std::path::Path;
 
 fn main() {
-    let pool = GenePool::new_dense(3, 1);
-    let mut population = Population::new(pool, Path::new("config.json")).unwrap();
-    let none = vec![0.0, 0.0, 1.0];
-    let first = vec![1.0, 0.0, 1.0];
-    let second = vec![0.0, 1.0, 1.0];
-    let both = vec![1.0, 1.0, 1.0];
-    let mut organism = population
-        .evolve(
-            |organisms| {
-                organisms.iter_mut().for_each(|organism| {
-                    let mut score = 0.0;
+    let pool = GenePool::new_dense(2, 1);
+    let config = ConfigLoader::new()
+        .file(Path::new("config.json"))
+        .and_then(|loader| loader.env())
+        .and_then(|loader| loader.args(std::env::args().skip(1)))
+        .and_then(|loader| loader.load())
+        .unwrap();
+    let mut population = Population::with_config(pool, config).unwrap();
+    for warning in population.config_warnings() {
+        eprintln!("warning: {}", warning);
+    }
+    population.add_observer(StdoutObserver);
+    population
+        .write_generations(Path::new("xor_population"), FileType::Bincode)
+        .unwrap();
+    let none = vec![0.0, 0.0];
+    let first = vec![1.0, 0.0];
+    let second = vec![0.0, 1.0];
+    let both = vec![1.0, 1.0];
+    let organism = population
+        .evolve_parallel(|organism| {
+            let mut score = 0.0;
 
-                    let result = organism.evaluate(&none);
-                    score += (result[0] - 0.0).powi(2);
+            let result = organism.evaluate(&none).unwrap();
+            score += (result[0] - 0.0).powi(2);
 
-                    let result = organism.evaluate(&first);
-                    score += (result[0] - 1.0).powi(2);
+            let result = organism.evaluate(&first).unwrap();
+            score += (result[0] - 1.0).powi(2);
 
-                    let result = organism.evaluate(&second);
-                    score += (result[0] - 1.0).powi(2);
+            let result = organism.evaluate(&second).unwrap();
+            score += (result[0] - 1.0).powi(2);
 
-                    let result = organism.evaluate(&both);
-                    score += (result[0] - 0.0).powi(2);
+            let result = organism.evaluate(&both).unwrap();
+            score += (result[0] - 0.0).powi(2);
 
-                    organism.fitness = Some(4.0 - score);
-                })
-            },
-            Path::new("xor_population"),
-        )
+            4.0 - score
+        })
         .unwrap();
-    println!("");
+    println!();
     println!("=========================================================================");
     println!("{:?}", organism);
     println!("=========================================================================");
-    println!("");
-    println!("0 xor 0: {:.3}", organism.evaluate(&none)[0]);
-    println!("1 xor 0: {:.3}", organism.evaluate(&first)[0]);
-    println!("0 xor 1: {:.3}", organism.evaluate(&second)[0]);
-    println!("1 xor 1: {:.3}", organism.evaluate(&both)[0]);
+    println!();
+    println!("0 xor 0: {:.3}", organism.evaluate(&none).unwrap()[0]);
+    println!("1 xor 0: {:.3}", organism.evaluate(&first).unwrap()[0]);
+    println!("0 xor 1: {:.3}", organism.evaluate(&second).unwrap()[0]);
+    println!("1 xor 1: {:.3}", organism.evaluate(&both).unwrap()[0]);
 
     /*
     let mut pool = GenePool::new();
@@ -0,0 +1,306 @@
+use rand::{prelude::SliceRandom, Rng};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Validate, Validation},
+    error::NeatError,
+    organism::Organism,
+};
+
+// Bewertet die Organismen einer Generation, indem sie in Zwei-Spieler-Spielen gegeneinander antreten.
+// Das Spiel liefert die Punkte beider Spieler, daraus wird nach der gewählten Wertung die Fitness berechnet.
+// Für Elo und Glicko entspricht das Ergebnis des ersten Spielers first / (first + second), ein Sieg sollte also
+// (1, 0) und ein Unentschieden (0.5, 0.5) liefern. Die Spiele einer Runde werden parallel ausgetragen.
+pub struct Coevolution {
+    config: CoevolutionConfig,
+    hall_of_fame: Vec<Organism>, // Die besten Organismen der vorherigen Generationen (der neueste zuletzt)
+}
+
+impl Coevolution {
+    pub fn new(config: CoevolutionConfig) -> Result<Self, NeatError> {
+        config.validate()?;
+        Ok(Coevolution {
+            config,
+            hall_of_fame: Vec::new(),
+        })
+    }
+
+    pub fn hall_of_fame(&self) -> &[Organism] {
+        &self.hall_of_fame
+    }
+
+    pub fn evaluate<G, R>(&mut self, organisms: &mut [Organism], game: G, rng: &mut R)
+    where
+        G: Fn(&Organism, &Organism) -> (f64, f64) + Sync,
+        R: Rng,
+    {
+        if organisms.is_empty() {
+            return;
+        }
+
+        // Die Teilnehmer sind zuerst die Organismen und danach die Mitglieder der Hall of Fame
+        let mut players = vec![Player::new(); organisms.len() + self.hall_of_fame.len()];
+        let participants: Vec<&Organism> = organisms.iter().chain(&self.hall_of_fame).collect();
+
+        for round in 0..self.config.schedule.round_count() {
+            let pairings = self.pairings(organisms.len(), round, &mut players, rng);
+            let results: Vec<(f64, f64)> = pairings
+                .par_iter()
+                .map(|(first, second)| game(participants[*first], participants[*second]))
+                .collect();
+            self.config.rating.update(&mut players, &pairings, &results);
+        }
+
+        for (organism, player) in organisms.iter_mut().zip(&players) {
+            organism.fitness = Some(self.config.rating.fitness(player));
+        }
+
+        if let Schedule::HallOfFame { size } = self.config.schedule {
+            let best = organisms.iter().max().unwrap().clone();
+            self.hall_of_fame.push(best);
+            if self.hall_of_fame.len() > size {
+                self.hall_of_fame.remove(0);
+            }
+        }
+    }
+
+    // Paarungen der Runde als Indizes in die Teilnehmer
+    fn pairings<R: Rng>(
+        &self,
+        organism_count: usize,
+        round: usize,
+        players: &mut [Player],
+        rng: &mut R,
+    ) -> Vec<(usize, usize)> {
+        let mut pairings = Vec::new();
+        match self.config.schedule {
+            Schedule::RoundRobin => {
+                for i in 0..organism_count {
+                    for j in i + 1..organism_count {
+                        pairings.push((i, j));
+                    }
+                }
+            }
+            Schedule::RandomOpponents { count } => {
+                for i in 0..organism_count {
+                    pairings.extend(random_opponents(i, organism_count, count, rng));
+                }
+            }
+            Schedule::Swiss { .. } => {
+                // In der ersten Runde werden die Paarungen ausgelost, danach spielen Organismen mit
+                // ähnlich vielen Punkten gegeneinander. Bei einer ungeraden Anzahl setzt der am schlechtesten
+                // platzierte Organismus aus, der noch nicht ausgesetzt hat (danach beginnt es von vorne).
+                let mut order: Vec<usize> = (0..organism_count).collect();
+                order.shuffle(rng);
+                if round > 0 {
+                    order.sort_by(|a, b| players[*b].score.total_cmp(&players[*a].score));
+                }
+                if order.len() % 2 == 1 {
+                    if order.iter().all(|i| players[*i].bye) {
+                        order.iter().for_each(|i| players[*i].bye = false);
+                    }
+                    let position = order.iter().rposition(|i| !players[*i].bye).unwrap();
+                    players[order.remove(position)].bye = true;
+                }
+                pairings.extend(order.chunks_exact(2).map(|pair| (pair[0], pair[1])));
+            }
+            Schedule::HallOfFame { size } => {
+                let hall_of_fame = organism_count..players.len();
+                let missing = size - hall_of_fame.len();
+                for i in 0..organism_count {
+                    pairings.extend(hall_of_fame.clone().map(|j| (i, j)));
+                    // Solange die Hall of Fame noch nicht gefüllt ist, werden zufällige Gegner ergänzt
+                    pairings.extend(random_opponents(i, organism_count, missing, rng));
+                }
+            }
+        }
+        pairings
+    }
+}
+
+fn random_opponents<R: Rng>(
+    player: usize,
+    organism_count: usize,
+    count: usize,
+    rng: &mut R,
+) -> Vec<(usize, usize)> {
+    if organism_count < 2 {
+        return Vec::new();
+    }
+    (0..count)
+        .map(|_| {
+            // Zufälliger Gegner außer dem Organismus selbst
+            let opponent = rng.gen_range(0..organism_count - 1);
+            if opponent >= player {
+                (player, opponent + 1)
+            } else {
+                (player, opponent)
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct Player {
+    score: f64, // Summe der Punkte
+    games: usize,
+    rating: f64,
+    deviation: f64, // Nur für Glicko
+    bye: bool,      // Ob der Spieler bei Swiss schon eine Runde ausgesetzt hat
+}
+
+impl Player {
+    fn new() -> Self {
+        Player {
+            score: 0.0,
+            games: 0,
+            rating: INITIAL_RATING,
+            deviation: INITIAL_DEVIATION,
+            bye: false,
+        }
+    }
+}
+
+const INITIAL_RATING: f64 = 1500.0;
+const INITIAL_DEVIATION: f64 = 350.0;
+const GLICKO_Q: f64 = std::f64::consts::LN_10 / 400.0;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoevolutionConfig {
+    pub schedule: Schedule, // Wer gegen wen spielt
+    pub rating: Rating,     // Wie aus den Ergebnissen die Fitness berechnet wird
+}
+
+impl Validate for CoevolutionConfig {
+    fn check(&self, validation: &mut Validation) {
+        match self.schedule {
+            Schedule::RandomOpponents { count } => validation.not_zero(count, "schedule.count"),
+            Schedule::Swiss { rounds } => validation.not_zero(rounds, "schedule.rounds"),
+            Schedule::HallOfFame { size } => validation.not_zero(size, "schedule.size"),
+            Schedule::RoundRobin => {}
+        }
+        if let Rating::Elo { k } = self.rating {
+            validation.not_negative(k, "rating.k");
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub enum Schedule {
+    #[default]
+    RoundRobin, // Jeder Organismus spielt gegen jeden anderen
+    RandomOpponents {
+        count: usize,
+    }, // Jeder Organismus spielt gegen count zufällige Gegner
+    Swiss {
+        rounds: usize,
+    }, // In jeder Runde spielen Organismen mit ähnlich vielen Punkten gegeneinander
+    HallOfFame {
+        size: usize,
+    }, // Jeder Organismus spielt gegen die besten Organismen der letzten size Generationen
+}
+
+impl Schedule {
+    fn round_count(&self) -> usize {
+        match self {
+            Schedule::Swiss { rounds } => *rounds,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub enum Rating {
+    #[default]
+    Score, // Mittlere Punktzahl pro Spiel
+    Elo {
+        k: f64,
+    }, // Elo Wertung (Startwert 1500), die nach jedem Spiel um höchstens k angepasst wird
+    Glicko, // Glicko Wertung (Startwert 1500), jede Runde ist ein Bewertungszeitraum
+}
+
+impl Rating {
+    fn update(&self, players: &mut [Player], pairings: &[(usize, usize)], results: &[(f64, f64)]) {
+        for ((first, second), (first_score, second_score)) in pairings.iter().zip(results) {
+            players[*first].score += first_score;
+            players[*first].games += 1;
+            players[*second].score += second_score;
+            players[*second].games += 1;
+        }
+
+        match self {
+            Rating::Score => {}
+            Rating::Elo { k } => {
+                for ((first, second), result) in pairings.iter().zip(results) {
+                    let outcome = outcome(*result);
+                    let expected = 1.0
+                        / (1.0
+                            + 10f64
+                                .powf((players[*second].rating - players[*first].rating) / 400.0));
+                    players[*first].rating += k * (outcome - expected);
+                    players[*second].rating -= k * (outcome - expected);
+                }
+            }
+            Rating::Glicko => {
+                // Alle Spieler werden mit den Wertungen vom Beginn der Runde aktualisiert
+                let previous = players.to_vec();
+                let mut games: Vec<Vec<(usize, f64)>> = vec![Vec::new(); players.len()];
+                for ((first, second), result) in pairings.iter().zip(results) {
+                    let outcome = outcome(*result);
+                    games[*first].push((*second, outcome));
+                    games[*second].push((*first, 1.0 - outcome));
+                }
+                for (player, games) in players.iter_mut().zip(games) {
+                    if !games.is_empty() {
+                        glicko_update(player, &games, &previous);
+                    }
+                }
+            }
+        }
+    }
+
+    fn fitness(&self, player: &Player) -> f64 {
+        match self {
+            Rating::Score => {
+                if player.games == 0 {
+                    0.0
+                } else {
+                    player.score / player.games as f64
+                }
+            }
+            Rating::Elo { .. } | Rating::Glicko => player.rating,
+        }
+    }
+}
+
+// Ergebnis aus Sicht des ersten Spielers in [0, 1]
+fn outcome((first, second): (f64, f64)) -> f64 {
+    if first + second == 0.0 {
+        0.5
+    } else {
+        first / (first + second)
+    }
+}
+
+fn glicko_update(player: &mut Player, games: &[(usize, f64)], previous: &[Player]) {
+    let g = |deviation: f64| {
+        1.0 / (1.0 + 3.0 * GLICKO_Q.powi(2) * deviation.powi(2) / std::f64::consts::PI.powi(2))
+            .sqrt()
+    };
+
+    let mut d_inverse = 0.0;
+    let mut sum = 0.0;
+    for (opponent, outcome) in games {
+        let opponent = &previous[*opponent];
+        let g = g(opponent.deviation);
+        let expected = 1.0 / (1.0 + 10f64.powf(-g * (player.rating - opponent.rating) / 400.0));
+        d_inverse += GLICKO_Q.powi(2) * g.powi(2) * expected * (1.0 - expected);
+        sum += g * (outcome - expected);
+    }
+
+    let denominator = 1.0 / player.deviation.powi(2) + d_inverse;
+    player.rating += GLICKO_Q / denominator * sum;
+    player.deviation = (1.0 / denominator).sqrt();
+}
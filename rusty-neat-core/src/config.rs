@@ -0,0 +1,314 @@
+use std::{fs, path::Path};
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{ConfigIssue, NeatError},
+    population::PopulationConfig,
+};
+
+// Präfix der Umgebungsvariablen, die einzelne Werte der Konfiguration überschreiben
+pub const ENV_PREFIX: &str = "NEAT_";
+
+// Lädt eine PopulationConfig aus mehreren Schichten. Spätere Schichten überschreiben frühere:
+// zuerst die Dateien in der Reihenfolge von file (z.B. eine Basis und ein Profil), danach die Überschreibungen
+// aus env und set bzw. args in der Reihenfolge der Aufrufe. Objekte werden zusammengeführt, alle anderen Werte
+// (auch Arrays) werden vollständig ersetzt. Fehlende Werte erhalten ihren Standardwert.
+pub struct ConfigLoader {
+    config: Value,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        ConfigLoader::new()
+    }
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self {
+        ConfigLoader {
+            config: Value::Object(Map::new()),
+        }
+    }
+
+    // Führt eine JSON, TOML oder YAML Datei (erkannt an der Endung) mit den bisherigen Schichten zusammen
+    pub fn file(mut self, path: &Path) -> Result<Self, NeatError> {
+        let layer = read_layer(path)?;
+        merge(&mut self.config, layer);
+        Ok(self)
+    }
+
+    // Wie file, eine fehlende Datei wird aber übersprungen (z.B. für optionale lokale Profile)
+    pub fn optional_file(self, path: &Path) -> Result<Self, NeatError> {
+        if path.exists() {
+            self.file(path)
+        } else {
+            Ok(self)
+        }
+    }
+
+    // Übernimmt alle Umgebungsvariablen der Form NEAT_<PFAD>=<WERT>, siehe env_vars
+    pub fn env(self) -> Result<Self, NeatError> {
+        self.env_vars(std::env::vars())
+    }
+
+    // Die Abschnitte des Pfads werden durch "__" getrennt, Zahlen sind Indizes in Arrays:
+    // NEAT_REPRODUCTION__MUTATION_TIERS__0__CONFIG__ADD_NODE_PROB entspricht
+    // reproduction.mutation_tiers[0].config.add_node_prob. Groß geschriebene Abschnitte sind Feldnamen und werden
+    // klein geschrieben, Abschnitte mit Kleinbuchstaben (Varianten wie Tournament) bleiben unverändert:
+    // NEAT_REPRODUCTION__SPECIES_STRATEGY__Tournament__SIZE. Variablen ohne das Präfix werden ignoriert.
+    pub fn env_vars<I: IntoIterator<Item = (String, String)>>(
+        mut self,
+        vars: I,
+    ) -> Result<Self, NeatError> {
+        for (name, value) in vars {
+            if let Some(path) = name.strip_prefix(ENV_PREFIX) {
+                let key = path
+                    .split("__")
+                    .map(|segment| match segment.parse::<usize>() {
+                        Ok(index) => format!("[{}]", index),
+                        Err(_) if segment.chars().any(|c| c.is_lowercase()) => segment.to_owned(),
+                        Err(_) => segment.to_lowercase(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(".")
+                    .replace(".[", "[");
+                self = self.set(&key, &value)?;
+            }
+        }
+        Ok(self)
+    }
+
+    // Übernimmt Kommandozeilenargumente der Form <PFAD>=<WERT>, siehe set
+    pub fn args<I: IntoIterator<Item = String>>(mut self, args: I) -> Result<Self, NeatError> {
+        for arg in args {
+            let (key, value) = arg.split_once('=').ok_or_else(|| {
+                NeatError::invalid_config(&arg, "overrides must have the form <path>=<value>")
+            })?;
+            self = self.set(key, value)?;
+        }
+        Ok(self)
+    }
+
+    // Setzt den Wert am Pfad key (z.B. "reproduction.mutation_tiers[0].config.add_node_prob"). value wird als
+    // JSON interpretiert und andernfalls als String übernommen, sodass auch Varianten wie First ohne
+    // Anführungszeichen angegeben werden können.
+    pub fn set(mut self, key: &str, value: &str) -> Result<Self, NeatError> {
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_owned()));
+        let mut target = &mut self.config;
+        for segment in parse_key(key)? {
+            target = match segment {
+                Segment::Field(field) => {
+                    if !target.is_object() {
+                        // null (z.B. ein nicht gesetzter optionaler Abschnitt) wird durch ein Objekt ersetzt
+                        if !target.is_null() {
+                            return Err(NeatError::invalid_config(key, "is not an object"));
+                        }
+                        *target = Value::Object(Map::new());
+                    }
+                    target
+                        .as_object_mut()
+                        .unwrap()
+                        .entry(field)
+                        .or_insert(Value::Null)
+                }
+                Segment::Index(index) => target
+                    .as_array_mut()
+                    .and_then(|array| array.get_mut(index))
+                    .ok_or_else(|| {
+                        NeatError::invalid_config(key, "refers to a missing array element")
+                    })?,
+            };
+        }
+        *target = value;
+        Ok(self)
+    }
+
+    // Erzeugt und prüft die Konfiguration aus allen Schichten
+    pub fn load(self) -> Result<PopulationConfig, NeatError> {
+        let config: PopulationConfig = deserialize(self.config)?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+// Zerlegt einen Pfad wie "a.b[1].c" in seine Abschnitte
+fn parse_key(key: &str) -> Result<Vec<Segment>, NeatError> {
+    let invalid = || NeatError::invalid_config(key, "is not a valid path");
+    let mut segments = Vec::new();
+    for part in key.split('.') {
+        let (field, mut indices) = match part.find('[') {
+            Some(start) => (&part[..start], &part[start..]),
+            None => (part, ""),
+        };
+        if field.is_empty() {
+            return Err(invalid());
+        }
+        segments.push(Segment::Field(field.to_owned()));
+        while !indices.is_empty() {
+            let end = indices.find(']').ok_or_else(invalid)?;
+            let index = indices[1..end].parse().map_err(|_| invalid())?;
+            segments.push(Segment::Index(index));
+            indices = &indices[end + 1..];
+            if !indices.is_empty() && !indices.starts_with('[') {
+                return Err(invalid());
+            }
+        }
+    }
+    Ok(segments)
+}
+
+fn read_layer(path: &Path) -> Result<Value, NeatError> {
+    let content = fs::read_to_string(path).map_err(|e| NeatError::io(path.to_owned(), e))?;
+    let serialization_error = |e| NeatError::Serialization {
+        path: path.to_owned(),
+        source: e,
+    };
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&content).map_err(|e| serialization_error(e.into())),
+        Some("toml") => toml::from_str(&content).map_err(|e| serialization_error(e.into())),
+        Some("yaml") | Some("yml") => {
+            serde_yaml::from_str(&content).map_err(|e| serialization_error(e.into()))
+        }
+        _ => Err(NeatError::UnknownFileExtension(path.to_owned())),
+    }
+}
+
+// Überträgt layer in config: Objekte werden rekursiv zusammengeführt, alle anderen Werte ersetzt
+fn merge(config: &mut Value, layer: Value) {
+    match (config, layer) {
+        (Value::Object(config), Value::Object(layer)) => {
+            for (key, value) in layer {
+                match config.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        config.insert(key, value);
+                    }
+                }
+            }
+        }
+        (config, layer) => *config = layer,
+    }
+}
+
+// Wie serde_json::from_value, ein Fehler nennt aber den Pfad des fehlerhaften Werts
+fn deserialize<T: DeserializeOwned>(value: Value) -> Result<T, NeatError> {
+    serde_path_to_error::deserialize(value)
+        .map_err(|e| NeatError::invalid_config(&e.path().to_string(), &e.into_inner().to_string()))
+}
+
+// Eine prüfbare Konfiguration. check meldet alle Probleme an validation, statt beim ersten Fehler abzubrechen.
+pub trait Validate {
+    fn check(&self, validation: &mut Validation);
+
+    // Gibt die Warnungen zurück oder alle Fehler als NeatError::InvalidConfig
+    fn validate(&self) -> Result<Vec<ConfigIssue>, NeatError> {
+        let mut validation = Validation::new();
+        self.check(&mut validation);
+        validation.finish()
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn check(&self, validation: &mut Validation) {
+        if let Some(config) = self {
+            config.check(validation);
+        }
+    }
+}
+
+// Sammelt die Fehler und Warnungen einer Konfiguration. Fehler machen die Konfiguration ungültig, Warnungen
+// weisen auf erlaubte, aber vermutlich ungewollte Einstellungen hin. Die Felder werden relativ zum aktuellen
+// Abschnitt (siehe section) angegeben.
+#[derive(Default)]
+pub struct Validation {
+    path: Vec<String>,
+    errors: Vec<ConfigIssue>,
+    warnings: Vec<ConfigIssue>,
+}
+
+impl Validation {
+    pub fn new() -> Self {
+        Validation::default()
+    }
+
+    pub fn error(&mut self, field: &str, message: &str) {
+        let issue = ConfigIssue::new(&self.full_path(field), message);
+        self.errors.push(issue);
+    }
+
+    pub fn warning(&mut self, field: &str, message: &str) {
+        let issue = ConfigIssue::new(&self.full_path(field), message);
+        self.warnings.push(issue);
+    }
+
+    // Führt check innerhalb des Abschnitts name aus (z.B. "reproduction" oder "mutation_tiers[0]")
+    pub fn section<F: FnOnce(&mut Self)>(&mut self, name: &str, check: F) {
+        self.path.push(name.to_owned());
+        check(self);
+        self.path.pop();
+    }
+
+    // Prüft eine verschachtelte Konfiguration im Abschnitt name
+    pub fn check<V: Validate + ?Sized>(&mut self, name: &str, config: &V) {
+        self.section(name, |validation| config.check(validation));
+    }
+
+    pub fn probability(&mut self, value: f64, field: &str) {
+        if !(0.0..=1.0).contains(&value) {
+            self.error(field, "must be a probability (in [0,1])");
+        }
+    }
+
+    pub fn ratio(&mut self, value: f64, field: &str) {
+        if !(0.0..=1.0).contains(&value) {
+            self.error(field, "must be a ratio (in [0,1])");
+        }
+    }
+
+    pub fn not_negative(&mut self, value: f64, field: &str) {
+        if value.is_nan() || value < 0.0 {
+            self.error(field, "must not be negative");
+        }
+    }
+
+    pub fn not_zero(&mut self, value: usize, field: &str) {
+        if value == 0 {
+            self.error(field, "must not be 0");
+        }
+    }
+
+    pub fn errors(&self) -> &[ConfigIssue] {
+        &self.errors
+    }
+
+    pub fn warnings(&self) -> &[ConfigIssue] {
+        &self.warnings
+    }
+
+    pub fn finish(self) -> Result<Vec<ConfigIssue>, NeatError> {
+        if self.errors.is_empty() {
+            Ok(self.warnings)
+        } else {
+            Err(NeatError::InvalidConfig(self.errors))
+        }
+    }
+
+    // Verbindet die Abschnitte mit '.', Indizes wie "[0]" werden direkt angehängt
+    fn full_path(&self, field: &str) -> String {
+        let mut path = String::new();
+        for segment in self.path.iter().map(String::as_str).chain([field]) {
+            if !path.is_empty() && !segment.is_empty() && !segment.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(segment);
+        }
+        path
+    }
+}
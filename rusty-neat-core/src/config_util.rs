@@ -1,31 +1,10 @@
 use rand_distr::Normal;
-use serde::{Deserialize, Serialize};
-
-pub(crate) fn assert_probability(value: f64, name: &str) -> Result<(), String> {
-    if value >= 0.0 && value <= 1.0 {
-        Ok(())
-    } else {
-        Err(name.to_owned() + " must be a probabilty (in [0,1])")
-    }
-}
-
-pub(crate) fn assert_not_negative(value: f64, name: &str) -> Result<(), String> {
-    if value >= 0.0 {
-        Ok(())
-    } else {
-        Err(name.to_owned() + " must not be negative")
-    }
-}
 
-pub(crate) fn assert_ratio(value: f64, name: &str) -> Result<(), String> {
-    if value >= 0.0 && value <= 1.0 {
-        Ok(())
-    } else {
-        Err(name.to_owned() + " must be a ratio (in [0,1])")
-    }
-}
+use crate::config::{Validate, Validation};
+use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(deny_unknown_fields)]
 pub struct NormalDistribution {
     pub mean: f64,
     pub std_dev: f64,
@@ -36,3 +15,17 @@ impl NormalDistribution {
         Normal::new(self.mean, self.std_dev).unwrap()
     }
 }
+
+impl Validate for NormalDistribution {
+    fn check(&self, validation: &mut Validation) {
+        if !self.mean.is_finite() {
+            validation.error("mean", "must be finite");
+        }
+        // Normal::new lehnt negative und nicht endliche Standardabweichungen ab
+        if !self.std_dev.is_finite() {
+            validation.error("std_dev", "must be finite");
+        } else {
+            validation.not_negative(self.std_dev, "std_dev");
+        }
+    }
+}
@@ -1,22 +1,36 @@
-use std::usize;
-
 use hashbrown::HashMap;
+use rand::Rng;
 use rusty_neat_interchange::gene_pool::{
     PrintableConnection, PrintableGenePool, PrintableNode, PrintableNodeType,
 };
+use serde::{Deserialize, Serialize};
 
-use crate::genome::{Genome, NewConnectionWeight};
+use crate::{
+    activation::Activation,
+    config::{Validate, Validation},
+    genome::{Genome, NewConnectionWeight},
+};
 
 const INPUT_NODE_DEPTH: f64 = 0.0;
 const OUTPUT_NODE_DEPTH: f64 = 1.0;
+const HIDDEN_NODE_DEPTH: f64 = 0.5;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GenePool {
     pub nodes: Vec<Node>,             // Liste aller Nodes über alle Genomes hinweg
     pub connections: Vec<Connection>, // Liste aller Connections über alle Genomes hinweg (Index = innovation der Connection)
     pub connection_mappings: HashMap<(usize, usize), Connection>, // (from, to) -> innovation
     pub input_count: usize,
     pub output_count: usize,
+    pub allow_recurrent: bool, // Ob Rückwärtskanten (from.depth >= to.depth) erzeugt werden dürfen
+    pub default_activation: Activation, // Aktivierungsfunktion neuer Output- und Hidden-Nodes in den Genomen
+    pub default_bias: f64,              // Bias neuer Output- und Hidden-Nodes in den Genomen
+}
+
+impl Default for GenePool {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GenePool {
@@ -27,6 +41,9 @@ impl GenePool {
             connections: Vec::new(),
             input_count: 0,
             output_count: 0,
+            allow_recurrent: false,
+            default_activation: Activation::Sigmoid,
+            default_bias: 0.0,
         }
     }
 
@@ -53,11 +70,7 @@ impl GenePool {
         }
 
         for connection in &printable.connections {
-            let connection = Connection {
-                from: connection.from as usize,
-                to: connection.to as usize,
-                innovation: connection.innovation as usize,
-            };
+            let connection = Connection::from(connection);
 
             pool.connections.insert(connection.innovation, connection);
             pool.connection_mappings
@@ -155,13 +168,19 @@ impl GenePool {
         if let Some(connection) = self.connection_mappings.get(&(from, to)) {
             Some(*connection)
         } else {
-            if self.nodes[from].depth >= self.nodes[to].depth {
+            // In Input Nodes dürfen keine Connections führen
+            if let NodeType::Input(_) = self.nodes[to].node_type {
+                return None;
+            }
+            let recurrent = self.nodes[from].depth >= self.nodes[to].depth;
+            if recurrent && !self.allow_recurrent {
                 return None;
             }
             let connection = Connection {
                 from,
                 to,
                 innovation: self.connections.len(),
+                recurrent,
             };
             self.connections.push(connection);
             self.connection_mappings.insert((from, to), connection);
@@ -169,31 +188,100 @@ impl GenePool {
         }
     }
 
-    pub fn new_genome(
+    // Fügt count Hidden-Nodes ein, die mit allen Inputs und Outputs verbunden werden
+    pub fn create_initial_hidden_nodes(&mut self, count: usize) {
+        let inputs: Vec<usize> = self
+            .nodes
+            .iter()
+            .filter(|node| matches!(node.node_type, NodeType::Input(_)))
+            .map(|node| node.id)
+            .collect();
+        let outputs: Vec<usize> = self
+            .nodes
+            .iter()
+            .filter(|node| matches!(node.node_type, NodeType::Output(_)))
+            .map(|node| node.id)
+            .collect();
+
+        for i in 0..count {
+            let hidden =
+                self.create_hidden_node(HIDDEN_NODE_DEPTH, (i as f64 + 0.5) / count as f64);
+            for input in &inputs {
+                self.create_connection(*input, hidden);
+            }
+            for output in &outputs {
+                self.create_connection(hidden, *output);
+            }
+        }
+    }
+
+    // Die initialen Genome enthalten alle Nodes des Gene Pools und je nach connections dessen Connections
+    pub fn new_genome<R: Rng>(
         &self,
         weight_strategy: &NewConnectionWeight,
+        connections: &InitialConnections,
         id: u64,
         generation: u32,
+        rng: &mut R,
     ) -> Genome {
-        let mut genome = Genome::new(id, generation, self.input_count, self.output_count);
+        let mut genome = Genome::new(
+            id,
+            generation,
+            self.input_count,
+            self.output_count,
+            self.default_activation,
+            self.default_bias,
+        );
 
         for node in self.nodes.iter().skip(self.input_count + self.output_count) {
-            genome.add_node(node.id);
+            genome.add_node(node.id, self.default_activation, self.default_bias);
         }
 
-        self.connections
-            .iter()
-            .for_each(|connection| genome.add_new_connection(*connection, weight_strategy));
+        for connection in &self.connections {
+            let selected = match connections {
+                InitialConnections::Full => true,
+                InitialConnections::Random { probability } => rng.gen_bool(*probability),
+                InitialConnections::Unconnected => false,
+            };
+            if selected {
+                genome.add_new_connection(*connection, weight_strategy, rng);
+            }
+        }
 
         genome
     }
 }
 
-impl Into<PrintableGenePool> for &GenePool {
-    fn into(self) -> PrintableGenePool {
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct InitialTopology {
+    pub connections: InitialConnections, // Welche Connections des Gene Pools die initialen Genome erhalten
+    pub hidden_nodes: usize, // So viele Hidden-Nodes werden vor der ersten Generation zwischen Inputs und Outputs eingefügt
+}
+
+impl Validate for InitialTopology {
+    fn check(&self, validation: &mut Validation) {
+        if let InitialConnections::Random { probability } = self.connections {
+            validation.probability(probability, "connections.probability");
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub enum InitialConnections {
+    #[default]
+    Full, // Alle Connections (bei new_dense ist jeder Input mit jedem Output verbunden)
+    Random {
+        probability: f64,
+    }, // Jede Connection unabhängig mit der Wahrscheinlichkeit probability
+    Unconnected, // Keine Connections, das Netzwerk wächst ausschließlich durch Mutationen
+}
+
+impl From<&GenePool> for PrintableGenePool {
+    fn from(pool: &GenePool) -> Self {
         PrintableGenePool {
-            nodes: self.nodes.iter().map(|n| n.into()).collect(),
-            connections: self.connections.iter().map(|c| c.into()).collect(),
+            nodes: pool.nodes.iter().map(|n| n.into()).collect(),
+            connections: pool.connections.iter().map(|c| c.into()).collect(),
         }
     }
 }
@@ -215,9 +303,9 @@ impl From<&PrintableNodeType> for NodeType {
     }
 }
 
-impl Into<PrintableNodeType> for &NodeType {
-    fn into(self) -> PrintableNodeType {
-        match self {
+impl From<&NodeType> for PrintableNodeType {
+    fn from(node_type: &NodeType) -> Self {
+        match node_type {
             NodeType::Input(id) => PrintableNodeType::Input(*id),
             NodeType::Hidden => PrintableNodeType::Hidden,
             NodeType::Output(id) => PrintableNodeType::Output(*id),
@@ -226,7 +314,7 @@ impl Into<PrintableNodeType> for &NodeType {
 }
 
 // id ist immer gleich dem Index der Node im GenePool!
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Node {
     pub id: usize,
     pub node_type: NodeType,
@@ -245,13 +333,13 @@ impl From<&PrintableNode> for Node {
     }
 }
 
-impl Into<PrintableNode> for &Node {
-    fn into(self) -> PrintableNode {
+impl From<&Node> for PrintableNode {
+    fn from(node: &Node) -> Self {
         PrintableNode {
-            id: self.id as u64,
-            node_type: (&self.node_type).into(),
-            depth: self.depth,
-            vertical_placement: self.vertical_placement,
+            id: node.id as u64,
+            node_type: (&node.node_type).into(),
+            depth: node.depth,
+            vertical_placement: node.vertical_placement,
         }
     }
 }
@@ -262,6 +350,7 @@ pub struct Connection {
     pub from: usize,
     pub to: usize,
     pub innovation: usize,
+    pub recurrent: bool, // Rückwärtskante (from.depth >= to.depth)
 }
 
 impl From<&PrintableConnection> for Connection {
@@ -270,16 +359,18 @@ impl From<&PrintableConnection> for Connection {
             from: printable.from as usize,
             to: printable.to as usize,
             innovation: printable.innovation as usize,
+            recurrent: printable.recurrent,
         }
     }
 }
 
-impl Into<PrintableConnection> for &Connection {
-    fn into(self) -> PrintableConnection {
+impl From<&Connection> for PrintableConnection {
+    fn from(connection: &Connection) -> Self {
         PrintableConnection {
-            innovation: self.innovation as u64,
-            from: self.from as u64,
-            to: self.to as u64,
+            innovation: connection.innovation as u64,
+            from: connection.from as u64,
+            to: connection.to as u64,
+            recurrent: connection.recurrent,
         }
     }
 }
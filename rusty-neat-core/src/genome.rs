@@ -1,20 +1,22 @@
 use core::fmt;
 use serde::{Deserialize, Serialize};
 use std::{
-    cmp::max,
-    collections::BTreeMap,
-    usize,
+    cmp::{max, min},
+    collections::{BTreeMap, BTreeSet},
 };
 
 use crate::{
     activation::Activation,
-    config_util::{self, NormalDistribution},
-    gene_pool::{Connection, GenePool, NodeType},
+    config::{Validate, Validation},
+    config_util::NormalDistribution,
+    error::NeatError,
+    gene_pool::{Connection, GenePool},
+    network::{Network, NetworkEdge, NetworkNode},
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use rand::{prelude::SliceRandom, Rng};
 use rand_distr::Distribution;
-use rusty_neat_interchange::genome::{PrintableConnectionGene, PrintableGenome};
+use rusty_neat_interchange::genome::{PrintableConnectionGene, PrintableGenome, PrintableNodeGene};
 
 pub(crate) struct GenomeIdGenerator {
     next_id: u64,
@@ -25,6 +27,15 @@ impl GenomeIdGenerator {
         GenomeIdGenerator { next_id: 0 }
     }
 
+    // Setzt die Vergabe nach der zuletzt vergebenen Id fort
+    pub(crate) fn starting_after(last_id: u64) -> Self {
+        GenomeIdGenerator { next_id: last_id }
+    }
+
+    pub(crate) fn last_id(&self) -> u64 {
+        self.next_id
+    }
+
     pub(crate) fn next_id(&mut self) -> u64 {
         self.next_id += 1;
         self.next_id
@@ -40,8 +51,6 @@ pub struct Genome {
     connection_mappings: BTreeMap<usize, usize>, // innovation -> Index in connections
     nodes: Vec<NodeGene>, // Die ersten Nodes sind die Input Nodes, darauf folgen die Output Nodes, und dann die Hidden nodes
     node_mappings: HashMap<usize, usize>, // node_id -> gene_id
-    input_nodes: Vec<usize>, // Index der Inputs in nodes (in der gleichen Reihenfolge wie bei evaluate)
-    output_nodes: Vec<usize>, // Index der Outputs in nodes (in der gleichen Reihenfolge wie bei evaluate)
     next_iteration: u64,
     generation: u32, // Die Generation, in der das Genom erstellt wurde
 }
@@ -52,6 +61,8 @@ impl Genome {
         generation: u32,
         input_node_count: usize,
         output_node_count: usize,
+        output_activation: Activation,
+        output_bias: f64,
     ) -> Genome {
         let mut genome = Genome {
             id,
@@ -62,12 +73,13 @@ impl Genome {
             nodes: Vec::new(),
             node_mappings: HashMap::new(),
             next_iteration: 1,
-            input_nodes: Vec::new(),
-            output_nodes: Vec::new(),
             generation,
         };
-        for i in 0..input_node_count + output_node_count {
-            genome.add_node(i);
+        for i in 0..input_node_count {
+            genome.add_node(i, Activation::Identity, 0.0);
+        }
+        for i in input_node_count..input_node_count + output_node_count {
+            genome.add_node(i, output_activation, output_bias);
         }
 
         genome
@@ -99,40 +111,48 @@ impl Genome {
         self.id
     }
 
-    pub fn add_node(&mut self, id: usize) {
+    pub fn add_node(&mut self, id: usize, activation: Activation, bias: f64) {
         if !self.node_mappings.contains_key(&id) {
             self.nodes.push(NodeGene {
                 node_id: id,
+                activation,
+                bias,
                 incoming_connections: Vec::new(),
-                evaluation: EvaluationValue {
-                    iteration: 0,
-                    value: 0.0,
-                },
+                evaluation: EvaluationValue::default(),
             });
             self.node_mappings.insert(id, self.nodes.len() - 1);
         }
     }
 
-    pub fn add_new_connection(
+    // Fügt die Node hinzu oder überschreibt Aktivierungsfunktion und Bias einer vorhandenen Node
+    fn set_node(&mut self, id: usize, activation: Activation, bias: f64) {
+        if let Some(index) = self.node_mappings.get(&id) {
+            self.nodes[*index].activation = activation;
+            self.nodes[*index].bias = bias;
+        } else {
+            self.add_node(id, activation, bias);
+        }
+    }
+
+    pub fn add_new_connection<R: Rng>(
         &mut self,
         connection: Connection,
         weight_strategy: &NewConnectionWeight,
+        rng: &mut R,
     ) {
-        let weight = NewConnectionWeight::sample_weight(weight_strategy);
+        let weight = NewConnectionWeight::sample_weight(weight_strategy, rng);
         self.add_connection(connection, weight, true);
     }
 
-    // Falls die Enden der Connection noch nicht vorhanden sind, werden diese hinzugefügt
+    // Die Enden der Connection müssen bereits im Genome vorhanden sein
     fn add_connection(&mut self, connection: Connection, weight: f64, enabled: bool) {
-        self.add_node(connection.from);
-        self.add_node(connection.to);
-
         self.connections.push(ConnectionGene {
             innovation: connection.innovation,
             weight,
             from: *self.node_mappings.get(&connection.from).unwrap(),
             to: *self.node_mappings.get(&connection.to).unwrap(),
             enabled,
+            recurrent: connection.recurrent,
         });
         let index = self.connections.len() - 1;
         self.connection_mappings
@@ -142,43 +162,176 @@ impl Genome {
             .push(index);
     }
 
-    pub fn evaluate(&mut self, input: &[f64], config: &EvaluationConfig) -> Vec<f64> {
-        for (i, value) in input.iter().enumerate() {
-            self.nodes[i].evaluation = EvaluationValue {
-                iteration: self.next_iteration,
-                value: *value,
+    // Wertet das Genome direkt aus. Für wiederholte Auswertungen ist das kompilierte Netzwerk (compile) schneller.
+    pub fn evaluate(
+        &mut self,
+        input: &[f64],
+        config: &EvaluationConfig,
+    ) -> Result<Vec<f64>, NeatError> {
+        if input.len() != self.input_node_count {
+            return Err(NeatError::InputLength {
+                expected: self.input_node_count,
+                actual: input.len(),
+            });
+        }
+        let result = match config.network_type {
+            NetworkType::FeedForward => self.evaluate_pass(input, false),
+            NetworkType::Timeline => {
+                // Rückwärtskanten lesen den Wert der letzten Auswertung
+                self.store_state();
+                self.evaluate_pass(input, true)
+            }
+            NetworkType::Stabilizing {
+                max_iterations,
+                tolerance,
+            } => {
+                // Im ersten Durchlauf liefern Rückwärtskanten 0
+                self.reset_state();
+                let mut result = self.evaluate_pass(input, true);
+                for _ in 1..max_iterations {
+                    self.store_state();
+                    result = self.evaluate_pass(input, true);
+                    if self.nodes.iter().all(|node| {
+                        (node.evaluation.value - node.evaluation.previous).abs() <= tolerance
+                    }) {
+                        break;
+                    }
+                }
+                result
             }
+        };
+        Ok(result)
+    }
+
+    // Setzt den Zustand rekurrenter Netzwerke (Werte der letzten Auswertung) zurück
+    pub fn reset_state(&mut self) {
+        self.nodes
+            .iter_mut()
+            .for_each(|node| node.evaluation = EvaluationValue::default());
+    }
+
+    fn store_state(&mut self) {
+        self.nodes
+            .iter_mut()
+            .for_each(|node| node.evaluation.previous = node.evaluation.value);
+    }
+
+    // Falls all_nodes gesetzt ist, werden auch Nodes ausgewertet, die nur über Rückwärtskanten
+    // mit den Outputs verbunden sind (sonst wäre ihr Wert in der nächsten Auswertung veraltet)
+    fn evaluate_pass(&mut self, input: &[f64], all_nodes: bool) -> Vec<f64> {
+        for (i, value) in input.iter().enumerate() {
+            self.nodes[i].evaluation.iteration = self.next_iteration;
+            self.nodes[i].evaluation.value = *value;
         }
         let mut result = Vec::with_capacity(self.output_node_count);
         for i in input.len()..input.len() + self.output_node_count {
-            result.push(self.evaluate_node(i, input, config));
+            result.push(self.evaluate_node(i));
+        }
+        if all_nodes {
+            for i in input.len() + self.output_node_count..self.nodes.len() {
+                self.evaluate_node(i);
+            }
         }
         self.next_iteration += 1;
         result
     }
 
     // node_id bezieht sich auf den Index im Genome
-    fn evaluate_node(&mut self, node_id: usize, input: &[f64], config: &EvaluationConfig) -> f64 {
+    fn evaluate_node(&mut self, node_id: usize) -> f64 {
         if self.nodes[node_id].evaluation.iteration == self.next_iteration {
             self.nodes[node_id].evaluation.value
         } else {
             let mut value = 0.0;
             for i in 0..self.nodes[node_id].incoming_connections.len() {
-                let connection = self.nodes[node_id].incoming_connections[i];
-                if self.connections[connection].enabled {
-                    let from = self.connections[connection].from;
-                    let weight = self.connections[connection].weight;
-                    value += weight * self.evaluate_node(from, input, config);
+                let connection = &self.connections[self.nodes[node_id].incoming_connections[i]];
+                if connection.enabled {
+                    let from = connection.from;
+                    let weight = connection.weight;
+                    if connection.recurrent {
+                        value += weight * self.nodes[from].evaluation.previous;
+                    } else {
+                        value += weight * self.evaluate_node(from);
+                    }
                 }
             }
-            value += config.bias;
-            value = (config.activation.function())(value);
+            value += self.nodes[node_id].bias;
+            value = (self.nodes[node_id].activation.function())(value);
             self.nodes[node_id].evaluation.iteration = self.next_iteration;
             self.nodes[node_id].evaluation.value = value;
             value
         }
     }
 
+    // Erzeugt das kompilierte Netzwerk (Phänotyp) des Genomes
+    pub fn compile(&self, config: &EvaluationConfig) -> Network {
+        // Topologische Sortierung (Kahn) anhand der aktivierten Vorwärtskanten. Da Vorwärtskanten immer
+        // zu einer größeren depth führen, enthalten diese keine Kreise.
+        let mut missing_inputs = vec![0; self.nodes.len()];
+        let mut outgoing_connections = vec![Vec::new(); self.nodes.len()];
+        for connection in &self.connections {
+            if connection.enabled && !connection.recurrent {
+                missing_inputs[connection.to] += 1;
+                outgoing_connections[connection.from].push(connection.to);
+            }
+        }
+        let mut ready: Vec<usize> = (0..self.nodes.len())
+            .filter(|node| missing_inputs[*node] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for to in &outgoing_connections[node] {
+                missing_inputs[*to] -= 1;
+                if missing_inputs[*to] == 0 {
+                    ready.push(*to);
+                }
+            }
+        }
+
+        // Die Inputs liegen am Anfang des Buffers, danach folgen die übrigen Nodes in topologischer Reihenfolge
+        let order: Vec<usize> = order
+            .into_iter()
+            .filter(|node| *node >= self.input_node_count)
+            .collect();
+        let mut positions: Vec<usize> = (0..self.nodes.len()).collect();
+        for (i, node) in order.iter().enumerate() {
+            positions[*node] = self.input_node_count + i;
+        }
+
+        let mut nodes = Vec::with_capacity(order.len());
+        let mut edges = Vec::with_capacity(self.connections.len());
+        for node in order {
+            let node = &self.nodes[node];
+            for connection in &node.incoming_connections {
+                let connection = &self.connections[*connection];
+                if connection.enabled {
+                    edges.push(NetworkEdge {
+                        from: positions[connection.from],
+                        weight: connection.weight,
+                        recurrent: connection.recurrent,
+                    });
+                }
+            }
+            nodes.push(NetworkNode {
+                bias: node.bias,
+                activation: node.activation.function(),
+                edges_end: edges.len(),
+            });
+        }
+
+        let outputs = (self.input_node_count..self.input_node_count + self.output_node_count)
+            .map(|node| positions[node])
+            .collect();
+
+        Network::new(
+            self.input_node_count,
+            nodes,
+            edges,
+            outputs,
+            config.network_type,
+        )
+    }
+
     pub fn distance(&self, other: &Genome, config: &DistanceConfig) -> f64 {
         let mut disjoint = 0;
         let mut similar = 0;
@@ -220,31 +373,69 @@ impl Genome {
         let excess = excess as f64;
         let n = max(self.connections.len(), other.connections.len()) as f64;
 
-        (disjoint * config.c1 + excess * config.c2) / n + weight_difference / similar * config.c3
+        // Bias-Unterschied der gemeinsamen Nodes (Input Nodes haben keinen Bias)
+        let mut similar_nodes = 0;
+        let mut bias_difference = 0.0;
+        for node in self.nodes.iter().skip(self.input_node_count) {
+            if let Some(index) = other.node_mappings.get(&node.node_id) {
+                bias_difference += (node.bias - other.nodes[*index].bias).abs();
+                similar_nodes += 1;
+            }
+        }
+        let bias_distance = if similar_nodes > 0 {
+            bias_difference / similar_nodes as f64 * config.c4
+        } else {
+            0.0
+        };
+
+        // Genome ohne (gemeinsame) Connections, wie sie bei spärlichen initialen Topologien entstehen
+        let structure_distance = if n > 0.0 {
+            (disjoint * config.c1 + excess * config.c2) / n
+        } else {
+            0.0
+        };
+        let weight_distance = if similar > 0.0 {
+            weight_difference / similar * config.c3
+        } else {
+            0.0
+        };
+
+        structure_distance + weight_distance + bias_distance
     }
 
-    pub fn mutate(
+    pub fn mutate<R: Rng>(
         &mut self,
         pool: &mut GenePool,
         config: &MutationConfig,
         new_id: u64,
         new_generation: u32,
+        rng: &mut R,
     ) {
         self.id = new_id;
         self.generation = new_generation;
-        self.mutate_connections(config);
+        self.reset_state();
+        self.mutate_connections(config, rng);
+        self.mutate_activations(config, rng);
+        self.mutate_biases(config, rng);
 
-        if rand::thread_rng().gen_bool(config.add_node_prob) {
-            self.mutate_add_node(pool, config);
+        if rng.gen_bool(config.add_node_prob) {
+            self.mutate_add_node(pool, config, rng);
         }
 
-        if rand::thread_rng().gen_bool(config.add_connection_prob) {
-            self.mutate_add_connection(pool, config);
+        if rng.gen_bool(config.add_connection_prob) {
+            self.mutate_add_connection(pool, config, rng);
+        }
+
+        if rng.gen_bool(config.delete_connection_prob) {
+            self.mutate_delete_connection(rng);
+        }
+
+        if rng.gen_bool(config.delete_node_prob) {
+            self.mutate_delete_node(rng);
         }
     }
 
-    fn mutate_connections(&mut self, config: &MutationConfig) {
-        let rng = &mut rand::thread_rng();
+    fn mutate_connections<R: Rng>(&mut self, config: &MutationConfig, rng: &mut R) {
         if rng.gen_bool(config.change_weight_prob) {
             self.connections.iter_mut().for_each(|connection| {
                 if rng.gen_bool(config.toggle_connection_prob) {
@@ -260,14 +451,50 @@ impl Genome {
         }
     }
 
-    fn mutate_add_node(&mut self, pool: &mut GenePool, config: &MutationConfig) {
+    fn mutate_activations<R: Rng>(&mut self, config: &MutationConfig, rng: &mut R) {
+        if config.activation_options.is_empty() {
+            return;
+        }
+        // Input Nodes werden nicht aktiviert
+        self.nodes
+            .iter_mut()
+            .skip(self.input_node_count)
+            .for_each(|node| {
+                if rng.gen_bool(config.change_activation_prob) {
+                    node.activation = *config.activation_options.choose(rng).unwrap();
+                }
+            });
+    }
+
+    fn mutate_biases<R: Rng>(&mut self, config: &MutationConfig, rng: &mut R) {
+        // Input Nodes haben keinen Bias
+        self.nodes
+            .iter_mut()
+            .skip(self.input_node_count)
+            .for_each(|node| {
+                if rng.gen_bool(config.change_bias_prob) {
+                    if rng.gen_bool(config.shift_bias_prob) {
+                        node.bias += config.shift_bias_dist.to_dist().sample(rng);
+                    } else {
+                        node.bias = config.random_bias_dist.to_dist().sample(rng);
+                    }
+                }
+            });
+    }
+
+    fn mutate_add_node<R: Rng>(
+        &mut self,
+        pool: &mut GenePool,
+        config: &MutationConfig,
+        rng: &mut R,
+    ) {
         // Falls das Genome keine Connections enthält, kann auch keine Node hinzugefügt werden
         if self.connections.is_empty() {
             return;
         }
 
         // Zufällige Connection auswählen
-        let index = rand::thread_rng().gen_range(0..self.connections.len());
+        let index = rng.gen_range(0..self.connections.len());
         let connection = &self.connections[index];
 
         let from = self.nodes[connection.from].node_id;
@@ -277,7 +504,7 @@ impl Genome {
 
         // Neue Node erstellen und zum Genome hinzufügen
         let new_node = pool.create_hidden_node_between(from, to);
-        self.add_node(new_node);
+        self.add_node(new_node, pool.default_activation, pool.default_bias);
 
         // Connection vom alten from zur neuen Node erstellen
         let left_connection = pool.create_connection(from, new_node).unwrap();
@@ -289,21 +516,26 @@ impl Genome {
 
         // Connection von der neuen Node zum alten to erstellen
         let right_connection = pool.create_connection(new_node, to).unwrap();
-        self.add_new_connection(right_connection, &config.new_connection_weight);
+        self.add_new_connection(right_connection, &config.new_connection_weight, rng);
 
         // Die alte Connection muss nicht (und darf nicht, falls sie später wieder enabled wird!)
         // aus den incoming_connections von to entfernt werden. Sie wird stattdessen disabled.
         self.connections[index].enabled = false;
     }
 
-    fn mutate_add_connection(&mut self, pool: &mut GenePool, config: &MutationConfig) {
+    fn mutate_add_connection<R: Rng>(
+        &mut self,
+        pool: &mut GenePool,
+        config: &MutationConfig,
+        rng: &mut R,
+    ) {
         if self.nodes.len() <= 1 {
             return;
         }
 
         for _ in 0..config.add_connection_retry_count {
-            let a = self.nodes.choose(&mut rand::thread_rng()).unwrap();
-            let b = self.nodes.choose(&mut rand::thread_rng()).unwrap();
+            let a = self.nodes.choose(rng).unwrap();
+            let b = self.nodes.choose(rng).unwrap();
 
             // Falls a links von b oder b links von a ist, funktioniert einer der Fälle
             if let Some(connection) = pool.create_connection(a.node_id, b.node_id) {
@@ -315,7 +547,7 @@ impl Genome {
                         continue;
                     }
                 } else {
-                    self.add_new_connection(connection, &config.new_connection_weight);
+                    self.add_new_connection(connection, &config.new_connection_weight, rng);
                 }
                 break;
             } else if let Some(connection) = pool.create_connection(b.node_id, a.node_id) {
@@ -327,86 +559,181 @@ impl Genome {
                         continue;
                     }
                 } else {
-                    self.add_new_connection(connection, &config.new_connection_weight);
+                    self.add_new_connection(connection, &config.new_connection_weight, rng);
+                }
+                break;
+            }
+        }
+    }
+
+    fn mutate_delete_connection<R: Rng>(&mut self, rng: &mut R) {
+        if self.connections.is_empty() {
+            return;
+        }
+        let index = rng.gen_range(0..self.connections.len());
+        self.remove(&[], &[index]);
+    }
+
+    // Entfernt eine zufällige Hidden-Node mit all ihren Connections
+    fn mutate_delete_node<R: Rng>(&mut self, rng: &mut R) {
+        let hidden_start = self.input_node_count + self.output_node_count;
+        if self.nodes.len() <= hidden_start {
+            return;
+        }
+        let node = rng.gen_range(hidden_start..self.nodes.len());
+        self.remove(&[node], &[]);
+    }
+
+    // Entfernt die Nodes und Connections (Indizes im Genome) sowie alle Connections der entfernten Nodes.
+    // Anschließend werden Hidden-Nodes ohne eingehende oder ohne ausgehende Connections entfernt, bis keine
+    // solche Node mehr übrig ist. Inputs und Outputs werden nie entfernt.
+    fn remove(&mut self, nodes: &[usize], connections: &[usize]) {
+        let hidden_start = self.input_node_count + self.output_node_count;
+        let mut removed_nodes = vec![false; self.nodes.len()];
+        nodes
+            .iter()
+            .for_each(|node| removed_nodes[*node] = *node >= hidden_start);
+        let mut removed_connections = vec![false; self.connections.len()];
+        connections
+            .iter()
+            .for_each(|connection| removed_connections[*connection] = true);
+
+        loop {
+            let mut incoming = vec![false; self.nodes.len()];
+            let mut outgoing = vec![false; self.nodes.len()];
+            for (index, connection) in self.connections.iter().enumerate() {
+                if removed_nodes[connection.from] || removed_nodes[connection.to] {
+                    removed_connections[index] = true;
                 }
+                if !removed_connections[index] {
+                    outgoing[connection.from] = true;
+                    incoming[connection.to] = true;
+                }
+            }
+
+            let mut changed = false;
+            for node in hidden_start..self.nodes.len() {
+                if !removed_nodes[node] && (!incoming[node] || !outgoing[node]) {
+                    removed_nodes[node] = true;
+                    changed = true;
+                }
+            }
+            if !changed {
                 break;
             }
         }
+
+        // Das Genome ohne die entfernten Gene neu aufbauen, da sich die Indizes verschieben
+        let old_nodes = std::mem::take(&mut self.nodes);
+        let old_connections = std::mem::take(&mut self.connections);
+        self.node_mappings.clear();
+        self.connection_mappings.clear();
+        for (node, _) in old_nodes
+            .iter()
+            .zip(&removed_nodes)
+            .filter(|(_, removed)| !**removed)
+        {
+            self.add_node(node.node_id, node.activation, node.bias);
+        }
+        for (connection, _) in old_connections
+            .iter()
+            .zip(&removed_connections)
+            .filter(|(_, removed)| !**removed)
+        {
+            self.add_connection(
+                Connection {
+                    from: old_nodes[connection.from].node_id,
+                    to: old_nodes[connection.to].node_id,
+                    innovation: connection.innovation,
+                    recurrent: connection.recurrent,
+                },
+                connection.weight,
+                connection.enabled,
+            );
+        }
     }
 
     fn get_connection_from_innovation(&self, innovation: usize) -> &ConnectionGene {
         &self.connections[*self.connection_mappings.get(&innovation).unwrap()]
     }
 
-    pub fn crossover(
-        &self,
-        other: &Genome,
+    // Kreuzt die Eltern nach config.operator. parents enthält die Genomes der Eltern mit ihrer Fitness, Union und
+    // Fitter kreuzen die ersten beiden. Die Gene, die der Nachkomme erhält, stammen von den strukturgebenden
+    // Eltern (siehe CrossoverOperator). Gemeinsame Gene werden über alle Eltern gekreuzt, die sie besitzen.
+    // Ohne Eltern gibt es keinen Nachkommen.
+    pub fn crossover<R: Rng>(
+        parents: &[(&Genome, f64)],
         pool: &GenePool,
         config: &CrossoverConfig,
         offspring_id: u64,
         offspring_generation: u32,
-    ) -> Genome {
+        rng: &mut R,
+    ) -> Option<Genome> {
+        if parents.is_empty() {
+            return None;
+        }
+
+        // Die Eltern absteigend nach Fitness sortieren, der erste ist der bessere Elternteil
+        let count = match config.operator {
+            CrossoverOperator::MultiParent { .. } => parents.len(),
+            _ => min(parents.len(), 2),
+        };
+        let mut parents = parents[..count].to_vec();
+        parents.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let structural = match config.operator {
+            CrossoverOperator::Union => parents.len(),
+            CrossoverOperator::Fitter | CrossoverOperator::MultiParent { .. } => parents
+                .iter()
+                .take_while(|(_, fitness)| *fitness == parents[0].1)
+                .count()
+                .max(1),
+        };
+        let parents: Vec<&Genome> = parents.into_iter().map(|(genome, _)| genome).collect();
+
+        let first = parents[0];
         let mut offspring = Genome::new(
             offspring_id,
             offspring_generation,
-            self.input_node_count,
-            self.output_node_count,
+            first.input_node_count,
+            first.output_node_count,
+            pool.default_activation,
+            pool.default_bias,
         );
 
-        let mut my_connections = self.connection_mappings.keys().peekable();
-        let mut other_connections = other.connection_mappings.keys().peekable();
-
-        while my_connections.peek().is_some() && other_connections.peek().is_some() {
-            if my_connections.peek().eq(&other_connections.peek()) {
-                // Similar
-                let my_gene = self.get_connection_from_innovation(*my_connections.next().unwrap());
-                let other_gene =
-                    other.get_connection_from_innovation(*other_connections.next().unwrap());
-                crossover_similar(my_gene, other_gene, &mut offspring, pool, config);
-            } else {
-                // Disjoint
-                if my_connections.peek().lt(&other_connections.peek()) {
-                    let my_gene =
-                        self.get_connection_from_innovation(*my_connections.next().unwrap());
-                    offspring.add_connection(
-                        pool.connections[my_gene.innovation],
-                        my_gene.weight,
-                        my_gene.enabled,
-                    );
-                } else {
-                    let other_gene =
-                        other.get_connection_from_innovation(*other_connections.next().unwrap());
-                    offspring.add_connection(
-                        pool.connections[other_gene.innovation],
-                        other_gene.weight,
-                        other_gene.enabled,
-                    );
+        // Nodes übernehmen. Bei Nodes, die in mehreren Eltern vorkommen, wird die Aktivierungsfunktion
+        // zufällig von einem Elternteil übernommen und der Bias wie ein Gewicht gekreuzt
+        let mut crossed = HashSet::new();
+        for parent in &parents[..structural] {
+            for node in &parent.nodes {
+                if !crossed.insert(node.node_id) {
+                    continue;
                 }
+                let holders: Vec<&NodeGene> = parents
+                    .iter()
+                    .filter_map(|p| p.node_mappings.get(&node.node_id).map(|i| &p.nodes[*i]))
+                    .collect();
+                let activation = holders[rng.gen_range(0..holders.len())].activation;
+                let biases: Vec<f64> = holders.iter().map(|n| n.bias).collect();
+                let bias = crossover_value(&biases, &config.weight_strategy, rng);
+                offspring.set_node(node.node_id, activation, bias);
             }
         }
 
-        // Excess
-        if my_connections.peek().is_some() {
-            for innovation in my_connections {
-                let my_gene = self.get_connection_from_innovation(*innovation);
-                offspring.add_connection(
-                    pool.connections[my_gene.innovation],
-                    my_gene.weight,
-                    my_gene.enabled,
-                );
-            }
-        } else if other_connections.peek().is_some() {
-            for innovation in other_connections {
-                let other_gene = other.get_connection_from_innovation(*innovation);
-                offspring.add_connection(
-                    pool.connections[other_gene.innovation],
-                    other_gene.weight,
-                    other_gene.enabled,
-                );
-            }
+        // Connections in der Reihenfolge der Innovation übernehmen
+        let innovations: BTreeSet<usize> = parents[..structural]
+            .iter()
+            .flat_map(|parent| parent.connection_mappings.keys().copied())
+            .collect();
+        for innovation in innovations {
+            let holders: Vec<&ConnectionGene> = parents
+                .iter()
+                .filter(|parent| parent.connection_mappings.contains_key(&innovation))
+                .map(|parent| parent.get_connection_from_innovation(innovation))
+                .collect();
+            crossover_similar(&holders, &mut offspring, pool, config, rng);
         }
 
-        offspring
+        Some(offspring)
     }
 
     pub fn get_status_of_connection(&self, node_id: usize) -> (f64, bool) {
@@ -420,13 +747,12 @@ impl Genome {
             printable_genome.generation,
             pool.input_count,
             pool.output_count,
+            pool.default_activation,
+            pool.default_bias,
         );
 
         for node in &printable_genome.nodes {
-            // Skip in and out nodes
-            if pool.nodes[*node as usize].node_type == NodeType::Hidden {
-                genome.add_node(*node as usize);   
-            }
+            genome.set_node(node.id as usize, (&node.activation).into(), node.bias);
         }
 
         for connection in &printable_genome.connections {
@@ -441,16 +767,24 @@ impl Genome {
     }
 }
 
-impl Into<PrintableGenome> for &Genome {
-    fn into(self) -> PrintableGenome {
+impl From<&Genome> for PrintableGenome {
+    fn from(genome: &Genome) -> Self {
         let mut printable = PrintableGenome {
-            id: self.id,
-            generation: self.generation,
+            id: genome.id,
+            generation: genome.generation,
             connections: Vec::new(),
-            nodes: self.node_mappings.keys().map(|n| *n as u64).collect(),
+            nodes: genome
+                .nodes
+                .iter()
+                .map(|node| PrintableNodeGene {
+                    id: node.node_id as u64,
+                    activation: (&node.activation).into(),
+                    bias: node.bias,
+                })
+                .collect(),
         };
 
-        for connection in &self.connections {
+        for connection in &genome.connections {
             printable.connections.push(PrintableConnectionGene {
                 innovation: connection.innovation as u64,
                 weight: connection.weight,
@@ -469,6 +803,7 @@ struct ConnectionGene {
     to: usize,   // Bezieht sich auf den Index im Genome
     weight: f64,
     enabled: bool,
+    recurrent: bool, // Rückwärtskante, liest den Wert der vorherigen Auswertung
 }
 
 impl fmt::Debug for ConnectionGene {
@@ -492,6 +827,8 @@ impl fmt::Debug for ConnectionGene {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct NodeGene {
     node_id: usize,
+    activation: Activation,
+    bias: f64,
     #[serde(skip)]
     incoming_connections: Vec<usize>, // Bezieht sich auf den Index im Genome
     #[serde(skip)]
@@ -502,30 +839,86 @@ struct NodeGene {
 struct EvaluationValue {
     iteration: u64,
     value: f64,
+    previous: f64, // Wert der vorherigen Auswertung (für Rückwärtskanten)
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
 pub struct DistanceConfig {
     pub c1: f64,
     pub c2: f64,
     pub c3: f64,
+    pub c4: f64, // Gewichtung des mittleren Bias-Unterschieds gemeinsamer Nodes
 }
 
-impl DistanceConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        config_util::assert_not_negative(self.c1, "c1")
-            .and(config_util::assert_not_negative(self.c2, "c2"))
-            .and(config_util::assert_not_negative(self.c3, "c3"))
+impl Default for DistanceConfig {
+    fn default() -> Self {
+        DistanceConfig {
+            c1: 1.0,
+            c2: 1.0,
+            c3: 0.3,
+            c4: 0.5,
+        }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Validate for DistanceConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.not_negative(self.c1, "c1");
+        validation.not_negative(self.c2, "c2");
+        validation.not_negative(self.c3, "c3");
+        validation.not_negative(self.c4, "c4");
+        if self.c1 == 0.0 && self.c2 == 0.0 && self.c3 == 0.0 && self.c4 == 0.0 {
+            validation.warning(
+                "",
+                "all coefficients are 0, every genome will be in the same species",
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
 pub struct EvaluationConfig {
-    pub bias: f64,
-    pub activation: Activation,
+    pub bias: f64,                 // Initialer Bias neuer Output- und Hidden-Nodes
+    pub activation: Activation,    // Aktivierungsfunktion neuer Output- und Hidden-Nodes
+    pub network_type: NetworkType, // Ob und wie Rückwärtskanten ausgewertet werden
 }
 
-#[derive(Serialize, Deserialize)]
+impl Validate for EvaluationConfig {
+    fn check(&self, validation: &mut Validation) {
+        if let NetworkType::Stabilizing {
+            max_iterations,
+            tolerance,
+        } = self.network_type
+        {
+            validation.not_zero(max_iterations as usize, "network_type.max_iterations");
+            validation.not_negative(tolerance, "network_type.tolerance");
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum NetworkType {
+    #[default]
+    FeedForward, // Erlaubt keine Rückwärtskanten / Kreise
+    Timeline, // Der Wert einer Rückwärtskante ist der der letzten Auswertung des Netzwerks. Das Netzwerk kann mit reset_state zurückgesetzt werden
+    Stabilizing {
+        // (Originale Implementierung) Rückwärtskanten werden im ersten Durchlauf nicht beachtet. Das Netzwerk wird solange erneut
+        // ausgewertet, bis sich kein Node-Wert mehr um mehr als tolerance ändert, höchstens aber max_iterations mal
+        max_iterations: u32,
+        tolerance: f64,
+    },
+}
+
+impl NetworkType {
+    pub fn allows_recurrence(&self) -> bool {
+        *self != NetworkType::FeedForward
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
 pub struct MutationConfig {
     pub change_weight_prob: f64, // Wahrscheinlichkeit, dass das Gewicht jedes einzelnen ConnectionGenes verändert wird
     pub random_weight_dist: NormalDistribution, // Standardabweichung s der N(0, s)-Verteilung für den zufälligen Wert der Connection bei einem weight change ohne shift
@@ -536,105 +929,222 @@ pub struct MutationConfig {
     pub add_connection_retry_count: u32, // Anzahl der Versuche, zwei passende Nodes für eine neue Connnection auszulosen
     pub new_connection_weight: NewConnectionWeight, // Wie das Gewicht einer neuen Connection festgelegt werden soll.
     pub toggle_connection_prob: f64, // Wahrscheinlichkeit, dass eine zufällige Connection enabled bzw. disabled wird
+    pub delete_connection_prob: f64, // Wahrscheinlichkeit, dass eine zufällige Connection entfernt wird (danach verwaiste Hidden-Nodes ebenfalls)
+    pub delete_node_prob: f64, // Wahrscheinlichkeit, dass eine zufällige Hidden-Node mit ihren Connections entfernt wird
+    pub change_activation_prob: f64, // Wahrscheinlichkeit, dass die Aktivierungsfunktion jeder einzelnen Node neu ausgelost wird
+    pub activation_options: Vec<Activation>, // Aus diesen Aktivierungsfunktionen wird bei einer Mutation gewählt
+    pub change_bias_prob: f64, // Wahrscheinlichkeit, dass der Bias jeder einzelnen Node verändert wird
+    pub random_bias_dist: NormalDistribution, // Verteilung für den zufälligen Wert des Bias bei einem bias change ohne shift
+    pub shift_bias_prob: f64, // Wahrscheinlichkeit, dass bias change den Bias shiftet und nicht zufällig neu setzt
+    pub shift_bias_dist: NormalDistribution, // Verteilung für den shift eines bias shifts
+}
+
+impl Default for MutationConfig {
+    fn default() -> Self {
+        MutationConfig {
+            change_weight_prob: 0.8,
+            random_weight_dist: NormalDistribution {
+                mean: 0.0,
+                std_dev: 3.0,
+            },
+            shift_weight_prob: 0.9,
+            shift_weight_dist: NormalDistribution {
+                mean: 0.0,
+                std_dev: 0.5,
+            },
+            add_node_prob: 0.03,
+            add_connection_prob: 0.05,
+            add_connection_retry_count: 100,
+            new_connection_weight: NewConnectionWeight::default(),
+            toggle_connection_prob: 0.08,
+            delete_connection_prob: 0.0,
+            delete_node_prob: 0.0,
+            change_activation_prob: 0.0,
+            activation_options: Vec::new(),
+            change_bias_prob: 0.5,
+            random_bias_dist: NormalDistribution {
+                mean: 0.0,
+                std_dev: 1.0,
+            },
+            shift_bias_prob: 0.9,
+            shift_bias_dist: NormalDistribution {
+                mean: 0.0,
+                std_dev: 0.5,
+            },
+        }
+    }
 }
 
 impl MutationConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        config_util::assert_probability(self.change_weight_prob, "change_weight_prob")
-            .and(config_util::assert_probability(
-                self.shift_weight_prob,
-                "shift_weight_prob",
-            ))
-            .and(config_util::assert_probability(
-                self.add_node_prob,
-                "add_node_prob",
-            ))
-            .and(config_util::assert_probability(
-                self.add_connection_prob,
-                "add_connection_prob",
-            ))
-            .and(config_util::assert_probability(
-                self.toggle_connection_prob,
-                "toggle_connection_prob",
-            ))
+    // Kopie, deren Verschiebungen von Gewichten und Bias mit factor skaliert sind (siehe AnnealingConfig)
+    pub fn annealed(&self, factor: f64) -> MutationConfig {
+        let mut config = self.clone();
+        config.shift_weight_dist.std_dev *= factor;
+        config.shift_bias_dist.std_dev *= factor;
+        config
     }
 }
 
-#[derive(Serialize, Deserialize)]
+impl Validate for MutationConfig {
+    fn check(&self, validation: &mut Validation) {
+        let probabilities = [
+            (self.change_weight_prob, "change_weight_prob"),
+            (self.shift_weight_prob, "shift_weight_prob"),
+            (self.add_node_prob, "add_node_prob"),
+            (self.add_connection_prob, "add_connection_prob"),
+            (self.toggle_connection_prob, "toggle_connection_prob"),
+            (self.delete_connection_prob, "delete_connection_prob"),
+            (self.delete_node_prob, "delete_node_prob"),
+            (self.change_activation_prob, "change_activation_prob"),
+            (self.change_bias_prob, "change_bias_prob"),
+            (self.shift_bias_prob, "shift_bias_prob"),
+        ];
+        for (probability, name) in probabilities {
+            validation.probability(probability, name);
+        }
+        if probabilities
+            .iter()
+            .all(|(probability, _)| *probability == 0.0)
+        {
+            validation.warning("", "all probabilities are 0, no mutation will ever happen");
+        }
+        if self.change_activation_prob > 0.0 && self.activation_options.is_empty() {
+            validation.error(
+                "activation_options",
+                "must not be empty if change_activation_prob is positive",
+            );
+        }
+        validation.check("random_weight_dist", &self.random_weight_dist);
+        validation.check("shift_weight_dist", &self.shift_weight_dist);
+        validation.check("new_connection_weight", &self.new_connection_weight);
+        validation.check("random_bias_dist", &self.random_bias_dist);
+        validation.check("shift_bias_dist", &self.shift_bias_dist);
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub enum NewConnectionWeight {
     Random(NormalDistribution),
     Fixed(f64),
 }
 
+impl Default for NewConnectionWeight {
+    fn default() -> Self {
+        NewConnectionWeight::Random(NormalDistribution {
+            mean: 0.0,
+            std_dev: 3.0,
+        })
+    }
+}
+
+impl Validate for NewConnectionWeight {
+    fn check(&self, validation: &mut Validation) {
+        if let NewConnectionWeight::Random(dist) = self {
+            validation.check("Random", dist);
+        }
+    }
+}
+
 impl NewConnectionWeight {
-    fn sample_weight(strategy: &NewConnectionWeight) -> f64 {
+    fn sample_weight<R: Rng>(strategy: &NewConnectionWeight, rng: &mut R) -> f64 {
         match strategy {
-            NewConnectionWeight::Random(dist) => dist.to_dist().sample(&mut rand::thread_rng()),
+            NewConnectionWeight::Random(dist) => dist.to_dist().sample(rng),
             &NewConnectionWeight::Fixed(value) => value,
         }
     }
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CrossoverConfig {
     pub disable_connection_prob: f64, // Wahrscheinlichkeit, dass eine Connection disabled wird, wenn die Connection in einem Elternteil disabled ist
     pub weight_strategy: CrossoverWeightStrategy, // Wie das Gewicht einer Connection die in beiden Eltern vorhanden ist bestimmt werden soll
+    pub operator: CrossoverOperator, // Von welchen Eltern der Nachkomme seine Gene erhält
 }
 
-impl CrossoverConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        config_util::assert_probability(self.disable_connection_prob, "disable_connection_prob")
+impl Default for CrossoverConfig {
+    fn default() -> Self {
+        CrossoverConfig {
+            disable_connection_prob: 0.75,
+            weight_strategy: CrossoverWeightStrategy::Random,
+            operator: CrossoverOperator::Union,
+        }
     }
 }
 
-#[derive(Serialize, Deserialize)]
+impl Validate for CrossoverConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.probability(self.disable_connection_prob, "disable_connection_prob");
+        if let CrossoverWeightStrategy::Blend { alpha } = self.weight_strategy {
+            validation.not_negative(alpha, "weight_strategy.alpha");
+        }
+        if let CrossoverOperator::MultiParent { parents } = self.operator {
+            if parents < 2 {
+                validation.error("operator.parents", "must be at least 2");
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
 pub enum CrossoverWeightStrategy {
+    #[default]
     Random, // Gewicht von einem zufälligen Eltern
     Better, // Gewicht des besseren Elternteils
     Mean,   // Mittelwert der Elterngewichte
+    Blend {
+        alpha: f64,
+    }, // BLX-α: Zufälliger Wert zwischen den Elterngewichten, das Intervall wird um alpha mal seine Länge erweitert
 }
 
-/*
-/////////////// Später implementieren ////////////////////////////////
-pub enum NETWORK_TYPE {
-  FEED_FORWARD,         // Aktuelle Implementierung. Erlaubt keine Rückwärtskanten / Kreise
-  TIMELINE,             // Der Wert einer Rückwärtskante ist der der letzten Auswertung des Netzwerks. Das Netzwerk muss zurückgesetzt werden können
-  STABLILIZING          // (Originale Implementierung) Rückwärtskanten werden im ersten Durchlauf nicht beachtet. Das Netzwerk wird solange erneut
-                        // ausgewertet, bis jede Kante berechnet werden konnte
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub enum CrossoverOperator {
+    #[default]
+    Union, // Disjunkte und überzählige Gene stammen von beiden Eltern
+    Fitter, // Disjunkte und überzählige Gene stammen nur vom besseren Elternteil, bei gleicher Fitness von beiden
+    MultiParent {
+        parents: usize,
+    }, // Die Gene stammen vom besten von parents Eltern einer Spezies (bei gleicher Fitness von allen besten)
 }
-*/
 
 ///////////////////////////////////////// Crossovers ////////////////////////////////////////////////////////7
-fn crossover_similar(
-    better_gene: &ConnectionGene,
-    worse_gene: &ConnectionGene,
+// Kreuzt eine Connection, die in allen holders vorhanden ist (der erste ist der beste Elternteil)
+fn crossover_similar<R: Rng>(
+    holders: &[&ConnectionGene],
     offspring: &mut Genome,
     pool: &GenePool,
     config: &CrossoverConfig,
+    rng: &mut R,
 ) {
-    let weight = match config.weight_strategy {
-        CrossoverWeightStrategy::Random => {
-            if rand::thread_rng().gen_bool(0.5) {
-                better_gene.weight
-            } else {
-                worse_gene.weight
-            }
-        }
-        CrossoverWeightStrategy::Better => better_gene.weight,
-        CrossoverWeightStrategy::Mean => (better_gene.weight + worse_gene.weight) / 2.0,
+    let weights: Vec<f64> = holders.iter().map(|gene| gene.weight).collect();
+    let weight = crossover_value(&weights, &config.weight_strategy, rng);
+
+    let enabled = if holders
+        .iter()
+        .all(|gene| gene.enabled == holders[0].enabled)
+    {
+        holders[0].enabled
+    } else {
+        rng.gen_bool(1.0 - config.disable_connection_prob)
     };
+    offspring.add_connection(pool.connections[holders[0].innovation], weight, enabled);
+}
 
-    if better_gene.enabled == worse_gene.enabled {
-        offspring.add_connection(
-            pool.connections[better_gene.innovation],
-            weight,
-            better_gene.enabled,
-        );
-    } else {
-        offspring.add_connection(
-            pool.connections[better_gene.innovation],
-            weight,
-            rand::thread_rng().gen_bool(1.0 - config.disable_connection_prob),
-        );
+// Wert eines Gens, das in allen Eltern von values vorhanden ist (Gewicht oder Bias). Der erste Wert stammt vom
+// besten Elternteil.
+fn crossover_value<R: Rng>(values: &[f64], strategy: &CrossoverWeightStrategy, rng: &mut R) -> f64 {
+    if values.len() == 1 {
+        return values[0];
+    }
+    match strategy {
+        CrossoverWeightStrategy::Random => values[rng.gen_range(0..values.len())],
+        CrossoverWeightStrategy::Better => values[0],
+        CrossoverWeightStrategy::Mean => values.iter().sum::<f64>() / values.len() as f64,
+        CrossoverWeightStrategy::Blend { alpha } => {
+            let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let extension = alpha * (max - min);
+            rng.gen_range(min - extension..=max + extension)
+        }
     }
 }
@@ -0,0 +1,567 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    activation::Activation,
+    config::{Validate, Validation},
+    error::NeatError,
+    genome::NetworkType,
+    network::{Network, NetworkEdge, NetworkNode},
+    organism::Organism,
+};
+
+// Anzahl der Outputs des CPPN: Gewicht der Connection und Bias der Ziel-Node
+pub const CPPN_OUTPUT_COUNT: usize = 2;
+
+// HyperNEAT: Ein evolviertes Genome dient als CPPN, das für je zwei Punkte des Substrats das Gewicht der
+// Connection zwischen ihnen liefert. Die Inputs des CPPN sind die Koordinaten der Quelle gefolgt von den
+// Koordinaten des Ziels, der Gene Pool muss also mit cppn_input_count Inputs und CPPN_OUTPUT_COUNT Outputs
+// erzeugt werden. Der Bias einer Node ist der zweite Output des CPPN mit dem Ursprung als Quelle.
+pub struct HyperNeat {
+    substrate: Substrate,
+    config: HyperNeatConfig,
+}
+
+impl HyperNeat {
+    pub fn new(substrate: Substrate, config: HyperNeatConfig) -> Result<Self, NeatError> {
+        let mut validation = Validation::new();
+        validation.check("substrate", &substrate);
+        config.check(&mut validation);
+        if config.evolvable_substrate.is_some() && substrate.dimensions() != 2 {
+            validation.error(
+                "evolvable_substrate",
+                "requires a two dimensional substrate",
+            );
+        }
+        validation.finish()?;
+        Ok(HyperNeat { substrate, config })
+    }
+
+    pub fn substrate(&self) -> &Substrate {
+        &self.substrate
+    }
+
+    pub fn cppn_input_count(&self) -> usize {
+        2 * self.substrate.dimensions()
+    }
+
+    // Erzeugt das Netzwerk des Substrats mit den Gewichten aus dem CPPN des Organismus. Schlägt fehl, wenn das
+    // CPPN nicht cppn_input_count Inputs und CPPN_OUTPUT_COUNT Outputs hat.
+    pub fn build(&self, cppn: &Organism) -> Result<Network, NeatError> {
+        let network = cppn.network();
+        if network.input_count() != self.cppn_input_count() {
+            return Err(NeatError::InputLength {
+                expected: self.cppn_input_count(),
+                actual: network.input_count(),
+            });
+        }
+        if network.output_count() != CPPN_OUTPUT_COUNT {
+            return Err(NeatError::OutputLength {
+                expected: CPPN_OUTPUT_COUNT,
+                actual: network.output_count(),
+            });
+        }
+
+        let cppn = Cppn::new(network);
+        Ok(match &self.config.evolvable_substrate {
+            Some(evolvable) => self.build_evolvable(&cppn, evolvable),
+            None => self.build_layered(&cppn),
+        })
+    }
+
+    // Jede Schicht ist vollständig mit der nächsten verbunden (Inputs, Hidden-Schichten, Outputs)
+    fn build_layered(&self, cppn: &Cppn) -> Network {
+        let mut builder = NetworkBuilder::new(self.substrate.inputs.len());
+        let mut previous: Vec<(usize, &[f64])> = self
+            .substrate
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, point)| (i, point.as_slice()))
+            .collect();
+
+        let layers = self
+            .substrate
+            .hidden
+            .iter()
+            .map(|layer| (layer, self.config.hidden_activation))
+            .chain(std::iter::once((
+                &self.substrate.outputs,
+                self.config.output_activation,
+            )));
+        for (layer, activation) in layers {
+            let mut current = Vec::with_capacity(layer.len());
+            for point in layer {
+                let edges = previous
+                    .iter()
+                    .filter_map(|(index, source)| {
+                        self.weight(cppn.weight(source, point))
+                            .map(|weight| (*index, weight))
+                    })
+                    .collect();
+                let index = builder.add_node(self.bias(cppn, point), activation, edges);
+                current.push((index, point.as_slice()));
+            }
+            previous = current;
+        }
+
+        builder.build(previous.into_iter().map(|(index, _)| index).collect())
+    }
+
+    // ES-HyperNEAT: Die Hidden-Nodes werden an den Stellen mit hoher Varianz des CPPN gesucht. Ausgehend von den
+    // Inputs werden iteration_level mal neue Hidden-Nodes entdeckt, anschließend werden die Outputs mit den
+    // gefundenen Hidden-Nodes verbunden. Nodes, die nicht auf einem Pfad von einem Input zu einem Output liegen,
+    // werden entfernt. Connections zwischen Hidden-Nodes führen immer in Richtung größerer y-Koordinate, sodass
+    // das Netzwerk keine Kreise enthält. Die Hidden-Schichten des Substrats werden nicht verwendet.
+    fn build_evolvable(&self, cppn: &Cppn, config: &EvolvableSubstrateConfig) -> Network {
+        let quadtree = Quadtree { cppn, config };
+        let mut hidden: Vec<[f64; 2]> = Vec::new();
+        let mut hidden_indices: HashMap<(u64, u64), usize> = HashMap::new();
+        let mut connections: Vec<(Source, usize, f64)> = Vec::new(); // (Quelle, Hidden-Node, Gewicht)
+
+        let mut discover = |point: [f64; 2], hidden: &mut Vec<[f64; 2]>| {
+            *hidden_indices.entry(key(point)).or_insert_with(|| {
+                hidden.push(point);
+                hidden.len() - 1
+            })
+        };
+
+        // Connections von den Inputs zu neuen Hidden-Nodes
+        let mut unexplored = Vec::new();
+        for (input, point) in self.substrate.inputs.iter().enumerate() {
+            let source = [point[0], point[1]];
+            for (target, weight) in quadtree.connections(source, true) {
+                let known = hidden.len();
+                let index = discover(target, &mut hidden);
+                if index == known {
+                    unexplored.push(index);
+                }
+                connections.push((Source::Input(input), index, weight));
+            }
+        }
+
+        // Connections zwischen Hidden-Nodes
+        for _ in 0..config.iteration_level {
+            let mut next = Vec::new();
+            for source in unexplored {
+                for (target, weight) in quadtree.connections(hidden[source], true) {
+                    if target[1] <= hidden[source][1] {
+                        continue;
+                    }
+                    let known = hidden.len();
+                    let index = discover(target, &mut hidden);
+                    if index == known {
+                        next.push(index);
+                    }
+                    connections.push((Source::Hidden(source), index, weight));
+                }
+            }
+            unexplored = next;
+        }
+
+        // Connections von bekannten Hidden-Nodes zu den Outputs
+        let mut output_connections = Vec::new(); // (Hidden-Node, Output, Gewicht)
+        for (output, point) in self.substrate.outputs.iter().enumerate() {
+            for (source, weight) in quadtree.connections([point[0], point[1]], false) {
+                if let Some(index) = hidden_indices.get(&key(source)) {
+                    output_connections.push((*index, output, weight));
+                }
+            }
+        }
+
+        // Nur Hidden-Nodes behalten, die von einem Input erreicht werden und zu einem Output führen
+        let mut from_input = vec![false; hidden.len()];
+        let mut to_output = vec![false; hidden.len()];
+        let mut order: Vec<usize> = (0..hidden.len()).collect();
+        order.sort_by(|a, b| {
+            (hidden[*a][1], hidden[*a][0])
+                .partial_cmp(&(hidden[*b][1], hidden[*b][0]))
+                .unwrap()
+        });
+        for (source, target, _) in &connections {
+            if let Source::Input(_) = source {
+                from_input[*target] = true;
+            }
+        }
+        for node in &order {
+            for (source, target, _) in &connections {
+                if *source == Source::Hidden(*node) && from_input[*node] {
+                    from_input[*target] = true;
+                }
+            }
+        }
+        for (source, _, _) in &output_connections {
+            to_output[*source] = true;
+        }
+        for node in order.iter().rev() {
+            for (source, target, _) in &connections {
+                if *source == Source::Hidden(*node) && to_output[*target] {
+                    to_output[*node] = true;
+                }
+            }
+        }
+
+        // Die Hidden-Nodes in aufsteigender y-Koordinate, danach die Outputs
+        let input_count = self.substrate.inputs.len();
+        let mut builder = NetworkBuilder::new(input_count);
+        let mut positions: Vec<Option<usize>> = vec![None; hidden.len()];
+        for node in order {
+            if !from_input[node] || !to_output[node] {
+                continue;
+            }
+            let edges = connections
+                .iter()
+                .filter(|(_, target, _)| *target == node)
+                .filter_map(|(source, _, weight)| {
+                    let from = match source {
+                        Source::Input(input) => Some(*input),
+                        Source::Hidden(hidden) => positions[*hidden],
+                    };
+                    Some((from?, self.weight(*weight)?))
+                })
+                .collect();
+            positions[node] = Some(builder.add_node(
+                self.bias(cppn, &hidden[node]),
+                self.config.hidden_activation,
+                edges,
+            ));
+        }
+
+        let outputs = self
+            .substrate
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(output, point)| {
+                let edges = output_connections
+                    .iter()
+                    .filter(|(_, target, _)| *target == output)
+                    .filter_map(|(source, _, weight)| {
+                        Some((positions[*source]?, self.weight(*weight)?))
+                    })
+                    .collect();
+                builder.add_node(self.bias(cppn, point), self.config.output_activation, edges)
+            })
+            .collect();
+        builder.build(outputs)
+    }
+
+    // Gewichte unterhalb von weight_threshold werden nicht exprimiert, die übrigen auf max_weight skaliert
+    fn weight(&self, value: f64) -> Option<f64> {
+        if value.abs() < self.config.weight_threshold {
+            return None;
+        }
+        let scaled = (value.abs().min(1.0) - self.config.weight_threshold)
+            / (1.0 - self.config.weight_threshold).max(f64::EPSILON);
+        Some(value.signum() * scaled * self.config.max_weight)
+    }
+
+    fn bias(&self, cppn: &Cppn, point: &[f64]) -> f64 {
+        cppn.bias(point) * self.config.max_weight
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Source {
+    Input(usize),
+    Hidden(usize),
+}
+
+fn key(point: [f64; 2]) -> (u64, u64) {
+    (point[0].to_bits(), point[1].to_bits())
+}
+
+struct Cppn<'a> {
+    network: &'a Network,
+}
+
+impl<'a> Cppn<'a> {
+    fn new(network: &'a Network) -> Self {
+        Cppn { network }
+    }
+
+    fn query(&self, source: &[f64], target: &[f64]) -> [f64; CPPN_OUTPUT_COUNT] {
+        let input: Vec<f64> = source.iter().chain(target).copied().collect();
+        let mut output = [0.0; CPPN_OUTPUT_COUNT];
+        // Die Anzahl der Inputs und Outputs wurde in HyperNeat::build geprüft
+        self.network.evaluate(&input, &mut output).unwrap();
+        output
+    }
+
+    fn weight(&self, source: &[f64], target: &[f64]) -> f64 {
+        self.query(source, target)[0]
+    }
+
+    fn bias(&self, target: &[f64]) -> f64 {
+        self.query(&vec![0.0; target.len()], target)[1]
+    }
+}
+
+// Sammelt die Nodes des Substrat-Netzwerks in topologischer Reihenfolge
+struct NetworkBuilder {
+    input_count: usize,
+    nodes: Vec<NetworkNode>,
+    edges: Vec<NetworkEdge>,
+}
+
+impl NetworkBuilder {
+    fn new(input_count: usize) -> Self {
+        NetworkBuilder {
+            input_count,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    // edges enthält (Index im Buffer, Gewicht), gibt den Index der neuen Node im Buffer zurück
+    fn add_node(&mut self, bias: f64, activation: Activation, edges: Vec<(usize, f64)>) -> usize {
+        self.edges
+            .extend(edges.into_iter().map(|(from, weight)| NetworkEdge {
+                from,
+                weight,
+                recurrent: false,
+            }));
+        self.nodes.push(NetworkNode {
+            bias,
+            activation: activation.function(),
+            edges_end: self.edges.len(),
+        });
+        self.input_count + self.nodes.len() - 1
+    }
+
+    fn build(self, outputs: Vec<usize>) -> Network {
+        Network::new(
+            self.input_count,
+            self.nodes,
+            self.edges,
+            outputs,
+            NetworkType::FeedForward,
+        )
+    }
+}
+
+// Quadtree über [-1, 1]², dessen Blätter die Gewichte des CPPN von bzw. zu einem festen Punkt enthalten
+struct Quadtree<'a, 'b> {
+    cppn: &'a Cppn<'b>,
+    config: &'a EvolvableSubstrateConfig,
+}
+
+struct QuadNode {
+    center: [f64; 2],
+    width: f64, // Halbe Kantenlänge
+    weight: f64,
+    children: Vec<QuadNode>,
+}
+
+impl QuadNode {
+    fn leaves(&self, weights: &mut Vec<f64>) {
+        if self.children.is_empty() {
+            weights.push(self.weight);
+        } else {
+            self.children.iter().for_each(|child| child.leaves(weights));
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let mut weights = Vec::new();
+        self.leaves(&mut weights);
+        let mean = weights.iter().sum::<f64>() / weights.len() as f64;
+        weights.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / weights.len() as f64
+    }
+}
+
+impl Quadtree<'_, '_> {
+    // Die Punkte mit den ausgedrückten Connections von (outgoing) bzw. zu point und deren Gewichte
+    fn connections(&self, point: [f64; 2], outgoing: bool) -> Vec<([f64; 2], f64)> {
+        let root = self.divide(point, outgoing);
+        let mut connections = Vec::new();
+        self.extract(point, outgoing, &root, &mut connections);
+        connections
+    }
+
+    fn query(&self, point: [f64; 2], other: [f64; 2], outgoing: bool) -> f64 {
+        if outgoing {
+            self.cppn.weight(&point, &other)
+        } else {
+            self.cppn.weight(&other, &point)
+        }
+    }
+
+    fn divide(&self, point: [f64; 2], outgoing: bool) -> QuadNode {
+        let mut root = QuadNode {
+            center: [0.0, 0.0],
+            width: 1.0,
+            weight: 0.0,
+            children: Vec::new(),
+        };
+        self.subdivide(&mut root, 1, point, outgoing);
+        root
+    }
+
+    fn subdivide(&self, node: &mut QuadNode, level: u32, point: [f64; 2], outgoing: bool) {
+        let width = node.width / 2.0;
+        for (dx, dy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            let center = [node.center[0] + dx * width, node.center[1] + dy * width];
+            node.children.push(QuadNode {
+                center,
+                width,
+                weight: self.query(point, center, outgoing),
+                children: Vec::new(),
+            });
+        }
+
+        if level < self.config.initial_depth
+            || (level < self.config.max_depth && node.variance() > self.config.division_threshold)
+        {
+            for child in &mut node.children {
+                self.subdivide(child, level + 1, point, outgoing);
+            }
+        }
+    }
+
+    fn extract(
+        &self,
+        point: [f64; 2],
+        outgoing: bool,
+        node: &QuadNode,
+        connections: &mut Vec<([f64; 2], f64)>,
+    ) {
+        for child in &node.children {
+            if !child.children.is_empty() && child.variance() >= self.config.variance_threshold {
+                self.extract(point, outgoing, child, connections);
+                continue;
+            }
+
+            // Band Pruning: Nur Punkte, die sich deutlich von ihren Nachbarn unterscheiden
+            let [x, y] = child.center;
+            let difference =
+                |other: [f64; 2]| (child.weight - self.query(point, other, outgoing)).abs();
+            let horizontal = difference([x - child.width, y]).min(difference([x + child.width, y]));
+            let vertical = difference([x, y - child.width]).min(difference([x, y + child.width]));
+            if horizontal.max(vertical) > self.config.band_threshold {
+                connections.push((child.center, child.weight));
+            }
+        }
+    }
+}
+
+// Koordinaten der Nodes des Substrats, alle Punkte müssen die gleiche Dimension haben
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Substrate {
+    pub inputs: Vec<Vec<f64>>,
+    pub hidden: Vec<Vec<Vec<f64>>>, // Hidden-Schichten in der Reihenfolge der Auswertung
+    pub outputs: Vec<Vec<f64>>,
+}
+
+impl Substrate {
+    // Gleichmäßiges Gitter über [-1, 1]², zeilenweise
+    pub fn grid(columns: usize, rows: usize) -> Vec<Vec<f64>> {
+        let coordinate = |i: usize, count: usize| {
+            if count <= 1 {
+                0.0
+            } else {
+                -1.0 + 2.0 * i as f64 / (count - 1) as f64
+            }
+        };
+        (0..rows)
+            .flat_map(|row| {
+                (0..columns)
+                    .map(move |column| vec![coordinate(column, columns), coordinate(row, rows)])
+            })
+            .collect()
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.inputs.first().map_or(0, |point| point.len())
+    }
+}
+
+impl Validate for Substrate {
+    fn check(&self, validation: &mut Validation) {
+        if self.inputs.is_empty() || self.outputs.is_empty() {
+            validation.error("", "needs at least one input and one output");
+            return;
+        }
+        let dimensions = self.dimensions();
+        if dimensions != 2 && dimensions != 3 {
+            validation.error("", "must be two or three dimensional");
+        }
+        if self
+            .inputs
+            .iter()
+            .chain(self.hidden.iter().flatten())
+            .chain(&self.outputs)
+            .any(|point| point.len() != dimensions)
+        {
+            validation.error("", "all points must have the same dimension");
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HyperNeatConfig {
+    pub weight_threshold: f64, // Connections, für die das CPPN einen Betrag unter diesem Wert liefert, werden nicht erzeugt
+    pub max_weight: f64,       // Betrag des größten Gewichts und Bias im Substrat
+    pub hidden_activation: Activation,
+    pub output_activation: Activation,
+    pub evolvable_substrate: Option<EvolvableSubstrateConfig>, // Ist evolvable_substrate gesetzt, werden die Hidden-Nodes wie bei ES-HyperNEAT gesucht
+}
+
+impl Default for HyperNeatConfig {
+    fn default() -> Self {
+        HyperNeatConfig {
+            weight_threshold: 0.2,
+            max_weight: 3.0,
+            hidden_activation: Activation::Sigmoid,
+            output_activation: Activation::Sigmoid,
+            evolvable_substrate: None,
+        }
+    }
+}
+
+impl Validate for HyperNeatConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.ratio(self.weight_threshold, "weight_threshold");
+        validation.not_negative(self.max_weight, "max_weight");
+        validation.check("evolvable_substrate", &self.evolvable_substrate);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvolvableSubstrateConfig {
+    pub initial_depth: u32, // Bis zu dieser Tiefe wird der Quadtree immer geteilt
+    pub max_depth: u32,     // Maximale Tiefe des Quadtree
+    pub division_threshold: f64, // Ein Bereich wird weiter geteilt, wenn die Varianz der Gewichte darüber liegt
+    pub variance_threshold: f64, // Bereiche mit geringerer Varianz werden nicht weiter nach Connections durchsucht
+    pub band_threshold: f64, // Minimaler Unterschied zu den Nachbarn, damit eine Connection ausgedrückt wird
+    pub iteration_level: u32, // Wie oft von den gefundenen Hidden-Nodes aus weitere Hidden-Nodes gesucht werden
+}
+
+impl Default for EvolvableSubstrateConfig {
+    fn default() -> Self {
+        EvolvableSubstrateConfig {
+            initial_depth: 3,
+            max_depth: 5,
+            division_threshold: 0.03,
+            variance_threshold: 0.03,
+            band_threshold: 0.3,
+            iteration_level: 1,
+        }
+    }
+}
+
+impl Validate for EvolvableSubstrateConfig {
+    fn check(&self, validation: &mut Validation) {
+        if self.initial_depth == 0 || self.initial_depth > self.max_depth {
+            validation.error(
+                "initial_depth",
+                "must be at least 1 and not greater than max_depth",
+            );
+        }
+        validation.not_negative(self.division_threshold, "division_threshold");
+        validation.not_negative(self.variance_threshold, "variance_threshold");
+        validation.not_negative(self.band_threshold, "band_threshold");
+    }
+}
@@ -0,0 +1,288 @@
+use std::mem;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Validate, Validation},
+    error::NeatError,
+    gene_pool::GenePool,
+    genome::GenomeIdGenerator,
+    observer::{EvolutionControl, EvolutionObserver},
+    organism::{compare_fitness, Organism},
+    population::{splitmix64, Population, PopulationConfig},
+};
+
+// Insel-Modell: Mehrere Populationen (Inseln) entwickeln sich unabhängig voneinander, alle migration_interval
+// Generationen wandern die besten migrant_count Organismen jeder Insel entlang der Topologie aus. Die Migranten
+// ersetzen zufällige Organismen der neuen Generation ihrer Zielinsel und werden dort neu bewertet.
+// Alle Inseln teilen sich einen Gene Pool und die Genome Ids, damit die Genomes zwischen den Inseln kompatibel
+// sind. Der Gene Pool wird der jeweils aktiven Insel übergeben, er liegt sonst im IslandModel (siehe pool).
+pub struct IslandModel {
+    islands: Vec<Population>,
+    config: IslandConfig,
+    topology: Box<dyn MigrationTopology>,
+    pool: GenePool,
+    genome_id_generator: GenomeIdGenerator,
+    completed: bool, // Ob die aktuelle Generation aller Inseln abgeschlossen wurde
+}
+
+impl IslandModel {
+    // Erzeugt eine Insel je Konfiguration. Ist seed gesetzt, erhält jede Insel einen eigenen, aus seed und dem
+    // Index der Insel gemischten Seed, damit sich die Inseln unterscheiden.
+    pub fn new<T: MigrationTopology + 'static>(
+        pool: GenePool,
+        configs: Vec<PopulationConfig>,
+        config: IslandConfig,
+        topology: T,
+    ) -> Result<Self, NeatError> {
+        let mut validation = Validation::new();
+        config.check(&mut validation);
+        if configs.is_empty() {
+            validation.error("islands", "at least one island is required");
+        }
+        for (i, island) in configs.iter().enumerate() {
+            let field = |name: &str| format!("islands[{}].{}", i, name);
+            validation.check(&format!("islands[{}]", i), island);
+            // Die Genomes müssen auf jeder Insel mit dem gemeinsamen Gene Pool ausgewertet werden können
+            if island.initial_topology.hidden_nodes != configs[0].initial_topology.hidden_nodes {
+                validation.error(
+                    &field("initial_topology.hidden_nodes"),
+                    "must be the same on all islands",
+                );
+            }
+            if island.evaluation.network_type != configs[0].evaluation.network_type {
+                validation.error(
+                    &field("evaluation.network_type"),
+                    "must be the same on all islands",
+                );
+            }
+            if config.migrant_count > island.reproduction.organism_count {
+                validation.error(
+                    "migrant_count",
+                    &format!("must not exceed the organism_count of island {}", i),
+                );
+            }
+        }
+        validation.finish()?;
+
+        let mut islands = configs
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut island)| {
+                island.seed = island
+                    .seed
+                    .map(|seed| splitmix64(seed ^ splitmix64(i as u64)));
+                Population::with_config(pool.clone(), island)
+            })
+            .collect::<Result<Vec<Population>, NeatError>>()?;
+        let pool = mem::take(&mut islands[0].pool);
+        Ok(IslandModel {
+            islands,
+            config,
+            topology: Box::new(topology),
+            pool,
+            genome_id_generator: GenomeIdGenerator::new(),
+            completed: false,
+        })
+    }
+
+    pub fn islands(&self) -> &[Population] {
+        &self.islands
+    }
+
+    // Der gemeinsame Gene Pool aller Inseln
+    pub fn pool(&self) -> &GenePool {
+        &self.pool
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.islands[0].generation()
+    }
+
+    // Die Organismen der aktuellen Generation einer Insel, damit der Benutzer sie bewerten kann
+    pub fn organisms_mut(&mut self, island: usize) -> &mut [Organism] {
+        self.islands[island].organisms_mut()
+    }
+
+    pub fn add_observer<O: EvolutionObserver + 'static>(&mut self, island: usize, observer: O) {
+        self.islands[island].add_observer(observer);
+    }
+
+    // Der beste bereits bewertete Organismus der aktuellen Generation aller Inseln
+    pub fn best(&self) -> Option<&Organism> {
+        self.islands
+            .iter()
+            .filter_map(|island| island.best())
+            .max_by(|x, y| x.fitness.unwrap().total_cmp(&y.fitness.unwrap()))
+    }
+
+    // Erzeugt die nächste Generation aller Inseln wie Population::next_generation. Die Auswanderer werden aus
+    // der abgeschlossenen Generation gewählt und ersetzen Organismen der neuen Generation ihrer Zielinseln.
+    pub fn next_generation(&mut self) -> Result<EvolutionControl, NeatError> {
+        let initial = self.islands[0].organisms().is_empty();
+        if !initial && !self.completed && self.finish_generation()? == EvolutionControl::Stop {
+            return Ok(EvolutionControl::Stop);
+        }
+
+        let generation = if initial { 0 } else { self.generation() + 1 };
+        let migrants = if !initial && generation % self.config.migration_interval == 0 {
+            Some(self.emigrants())
+        } else {
+            None
+        };
+
+        for index in 0..self.islands.len() {
+            self.with_island(index, |island| island.next_generation())?;
+        }
+        self.completed = false;
+
+        if let Some(migrants) = migrants {
+            let island_count = self.islands.len();
+            let mut immigrants: Vec<Vec<Organism>> = vec![Vec::new(); island_count];
+            for (source, organisms) in migrants.into_iter().enumerate() {
+                for target in self.topology.targets(source, island_count) {
+                    if target != source {
+                        immigrants[target].extend(organisms.iter().cloned());
+                    }
+                }
+            }
+            for (island, organisms) in self.islands.iter_mut().zip(immigrants) {
+                island.immigrate(organisms);
+            }
+        }
+        Ok(EvolutionControl::Continue)
+    }
+
+    // Schließt die bewertete aktuelle Generation aller Inseln ab (siehe Population::finish_generation)
+    pub fn finish_generation(&mut self) -> Result<EvolutionControl, NeatError> {
+        let mut control = EvolutionControl::Continue;
+        for index in 0..self.islands.len() {
+            if self.with_island(index, |island| island.finish_generation())?
+                == EvolutionControl::Stop
+            {
+                control = EvolutionControl::Stop;
+            }
+        }
+        self.completed = true;
+        Ok(control)
+    }
+
+    // Wie Population::evolve: fitness_function bewertet nacheinander die Organismen jeder Insel. Die Evolution
+    // endet, sobald eine Insel ihre target_fitness oder max_generations erreicht.
+    pub fn evolve<F: FnMut(&mut [Organism])>(
+        &mut self,
+        mut fitness_function: F,
+    ) -> Result<Organism, NeatError> {
+        if self.islands[0].organisms().is_empty() {
+            self.next_generation()?;
+        }
+
+        loop {
+            if !self.completed {
+                for island in &mut self.islands {
+                    fitness_function(island.organisms_mut());
+                }
+            }
+
+            let finished = self.islands.iter().any(|island| {
+                let config = island.config();
+                let best_fitness = island.best().and_then(|o| o.fitness);
+                best_fitness.is_some_and(|fitness| fitness > config.target_fitness)
+                    || (config.max_generations != 0
+                        && island.generation() >= config.max_generations)
+            });
+            if finished {
+                if !self.completed {
+                    self.finish_generation()?;
+                }
+                break;
+            }
+
+            if self.next_generation()? == EvolutionControl::Stop {
+                break;
+            }
+        }
+
+        self.best().cloned().ok_or(NeatError::MissingFitness)
+    }
+
+    // Die besten migrant_count Organismen jeder Insel
+    fn emigrants(&self) -> Vec<Vec<Organism>> {
+        self.islands
+            .iter()
+            .map(|island| {
+                let mut organisms: Vec<&Organism> = island.organisms().iter().collect();
+                organisms.sort_by(|x, y| compare_fitness(y.fitness, x.fitness));
+                organisms
+                    .into_iter()
+                    .take(self.config.migrant_count)
+                    .cloned()
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Führt step mit der Insel aus, während sie den gemeinsamen Gene Pool und die Genome Ids besitzt
+    fn with_island<T, F: FnOnce(&mut Population) -> T>(&mut self, index: usize, step: F) -> T {
+        let island = &mut self.islands[index];
+        mem::swap(&mut self.pool, &mut island.pool);
+        mem::swap(
+            &mut self.genome_id_generator,
+            &mut island.genome_id_generator,
+        );
+        island.configure_pool();
+        let result = step(island);
+        mem::swap(&mut self.pool, &mut island.pool);
+        mem::swap(
+            &mut self.genome_id_generator,
+            &mut island.genome_id_generator,
+        );
+        result
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IslandConfig {
+    pub migration_interval: u32, // Alle migration_interval Generationen wandern Organismen aus
+    pub migrant_count: usize, // So viele der besten Organismen jeder Insel wandern in jede Zielinsel aus
+}
+
+impl Default for IslandConfig {
+    fn default() -> Self {
+        IslandConfig {
+            migration_interval: 10,
+            migrant_count: 2,
+        }
+    }
+}
+
+impl Validate for IslandConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.not_zero(self.migration_interval as usize, "migration_interval");
+    }
+}
+
+// Bestimmt, zwischen welchen Inseln Organismen wandern
+pub trait MigrationTopology {
+    // Die Inseln, in die die besten Organismen der Insel island auswandern (die Insel selbst wird ignoriert)
+    fn targets(&self, island: usize, island_count: usize) -> Vec<usize>;
+}
+
+// Jede Insel sendet an die nächste, die letzte an die erste
+pub struct Ring;
+
+impl MigrationTopology for Ring {
+    fn targets(&self, island: usize, island_count: usize) -> Vec<usize> {
+        vec![(island + 1) % island_count]
+    }
+}
+
+// Jede Insel sendet an alle anderen
+pub struct FullyConnected;
+
+impl MigrationTopology for FullyConnected {
+    fn targets(&self, island: usize, island_count: usize) -> Vec<usize> {
+        (0..island_count).filter(|&other| other != island).collect()
+    }
+}
@@ -1,7 +1,16 @@
 pub mod activation;
+pub mod coevolution;
+pub mod config;
 pub mod config_util;
+pub use rusty_neat_interchange::error;
 pub mod gene_pool;
 pub mod genome;
+pub mod hyperneat;
+pub mod island;
+pub mod multi_objective;
+pub mod network;
+pub mod novelty;
+pub mod observer;
 pub mod organism;
 pub mod population;
 pub mod reproduction;
@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Validate, Validation},
+    organism::Organism,
+};
+
+// Ordnet die Organismen nach NSGA-II: Die Organismen werden per Non-dominated Sorting in Pareto-Fronten
+// eingeteilt (Rang 0 ist die beste Front) und innerhalb einer Front nach der Crowding Distance unterschieden.
+// Daraus ergibt sich die Bewertung für die Reproduktion: Jede bessere Front liegt um 1 höher, innerhalb einer
+// Front werden Organismen in dünn besetzten Bereichen bevorzugt.
+pub(crate) fn rank(organisms: &mut [Organism], config: &MultiObjectiveConfig) {
+    let objectives: Vec<Vec<f64>> = organisms
+        .iter()
+        .map(|o| config.normalize(o.objectives.as_ref().unwrap()))
+        .collect();
+    let fronts = non_dominated_fronts(&objectives);
+    let front_count = fronts.len();
+
+    for (rank, front) in fronts.iter().enumerate() {
+        let distances = crowding_distances(&objectives, front);
+        for (index, distance) in front.iter().zip(distances) {
+            organisms[*index]
+                .set_pareto_rank(rank, (front_count - rank) as f64 - 1.0 / (2.0 + distance));
+        }
+    }
+}
+
+// Teilt die Punkte in Pareto-Fronten ein. Alle Ziele werden maximiert. Gibt die Indizes der Punkte je Front
+// zurück, beginnend mit der nicht dominierten Front.
+pub fn non_dominated_fronts(objectives: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let mut dominated_by: Vec<Vec<usize>> = vec![Vec::new(); objectives.len()]; // Welche Punkte i dominiert
+    let mut domination_count = vec![0; objectives.len()]; // Von wie vielen Punkten i dominiert wird
+    for i in 0..objectives.len() {
+        for j in i + 1..objectives.len() {
+            if dominates(&objectives[i], &objectives[j]) {
+                dominated_by[i].push(j);
+                domination_count[j] += 1;
+            } else if dominates(&objectives[j], &objectives[i]) {
+                dominated_by[j].push(i);
+                domination_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..objectives.len())
+        .filter(|i| domination_count[*i] == 0)
+        .collect();
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for i in &front {
+            for j in &dominated_by[*i] {
+                domination_count[*j] -= 1;
+                if domination_count[*j] == 0 {
+                    next.push(*j);
+                }
+            }
+        }
+        next.sort_unstable();
+        fronts.push(front);
+        front = next;
+    }
+    fronts
+}
+
+// Die Crowding Distance jedes Punktes der Front (in der Reihenfolge von front). Die Randpunkte jedes Ziels
+// erhalten eine unendliche Distanz.
+pub fn crowding_distances(objectives: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let mut distances = vec![0.0; front.len()];
+    if front.len() <= 2 {
+        distances.iter_mut().for_each(|d| *d = f64::INFINITY);
+        return distances;
+    }
+
+    let objective_count = objectives[front[0]].len();
+    let mut order: Vec<usize> = (0..front.len()).collect();
+    let values_per_objective = (0..objective_count).map(|objective| {
+        front
+            .iter()
+            .map(|i| objectives[*i][objective])
+            .collect::<Vec<f64>>()
+    });
+    for values in values_per_objective {
+        order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+
+        let (first, last) = (order[0], order[front.len() - 1]);
+        distances[first] = f64::INFINITY;
+        distances[last] = f64::INFINITY;
+        if values[last] == values[first] {
+            continue;
+        }
+        for k in 1..front.len() - 1 {
+            distances[order[k]] +=
+                (values[order[k + 1]] - values[order[k - 1]]) / (values[last] - values[first]);
+        }
+    }
+    distances
+}
+
+// Ob a b dominiert, also in keinem Ziel schlechter und in mindestens einem besser ist
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    let mut better = false;
+    for (a, b) in a.iter().zip(b) {
+        match a.total_cmp(b) {
+            Ordering::Less => return false,
+            Ordering::Greater => better = true,
+            Ordering::Equal => {}
+        }
+    }
+    better
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct MultiObjectiveConfig {
+    pub objectives: Vec<Objective>, // Richtung jedes Ziels, in der Reihenfolge von Organism::objectives
+}
+
+impl Validate for MultiObjectiveConfig {
+    fn check(&self, validation: &mut Validation) {
+        if self.objectives.is_empty() {
+            validation.error("objectives", "at least one objective is required");
+        }
+    }
+}
+
+impl MultiObjectiveConfig {
+    // Dreht die zu minimierenden Ziele um, sodass alle Ziele maximiert werden
+    fn normalize(&self, objectives: &[f64]) -> Vec<f64> {
+        objectives
+            .iter()
+            .zip(&self.objectives)
+            .map(|(value, objective)| match objective {
+                Objective::Maximize => *value,
+                Objective::Minimize => -value,
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Objective {
+    Maximize,
+    Minimize,
+}
@@ -0,0 +1,208 @@
+use std::cell::RefCell;
+
+use crate::{error::NeatError, genome::NetworkType};
+
+// Kompiliertes Netzwerk (Phänotyp) eines Genomes. Die Nodes sind topologisch sortiert und liegen
+// zusammen mit den Kanten in flachen Arrays, sodass eine Auswertung ohne Hash Maps, Rekursion
+// und Allokationen auskommt. Da evaluate nur &self benötigt, kann ein Netzwerk von mehreren Threads
+// gleichzeitig ausgewertet werden.
+#[derive(Clone, Debug)]
+pub struct Network {
+    input_count: usize,
+    nodes: Vec<NetworkNode>, // Alle Nodes außer den Inputs in topologischer Reihenfolge
+    edges: Vec<NetworkEdge>, // Eingehende Kanten, gruppiert nach der Ziel-Node (in der Reihenfolge von nodes)
+    outputs: Vec<usize>, // Index der Outputs im Buffer (in der gleichen Reihenfolge wie bei evaluate)
+    network_type: NetworkType,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct NetworkNode {
+    pub(crate) bias: f64,
+    pub(crate) activation: fn(f64) -> f64,
+    pub(crate) edges_end: usize, // Die eingehenden Kanten liegen in edges[edges_end der vorherigen Node..edges_end]
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct NetworkEdge {
+    pub(crate) from: usize, // Index im Buffer
+    pub(crate) weight: f64,
+    pub(crate) recurrent: bool, // Rückwärtskante, liest den Wert der vorherigen Auswertung
+}
+
+// Werte der Nodes eines Netzwerks. Bei rekurrenten Netzwerken (NetworkType::Timeline) bleibt der Zustand
+// zwischen zwei Auswertungen mit evaluate_with_state (bzw. Organism::evaluate) erhalten und kann mit reset
+// zurückgesetzt werden. evaluate und evaluate_batch beginnen bei jedem Aufruf mit einem frischen Zustand.
+#[derive(Clone, Debug, Default)]
+pub struct NetworkState {
+    values: Vec<f64>, // Buffer: zuerst die Inputs, dann die Nodes in der Reihenfolge von Network::nodes
+    previous: Vec<f64>, // Werte der vorherigen Auswertung (für Rückwärtskanten)
+}
+
+thread_local! {
+    static STATE: RefCell<NetworkState> = RefCell::new(NetworkState::default());
+}
+
+impl Network {
+    pub(crate) fn new(
+        input_count: usize,
+        nodes: Vec<NetworkNode>,
+        edges: Vec<NetworkEdge>,
+        outputs: Vec<usize>,
+        network_type: NetworkType,
+    ) -> Self {
+        Network {
+            input_count,
+            nodes,
+            edges,
+            outputs,
+            network_type,
+        }
+    }
+
+    pub fn input_count(&self) -> usize {
+        self.input_count
+    }
+
+    pub fn output_count(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn new_state(&self) -> NetworkState {
+        let mut state = NetworkState::default();
+        state.prepare(self.buffer_len());
+        state
+    }
+
+    // Wertet das Netzwerk mit einem frischen Zustand aus, Rückwärtskanten liefern also im ersten Durchlauf 0.
+    // Soll der Zustand zwischen Auswertungen erhalten bleiben, muss evaluate_with_state verwendet werden.
+    pub fn evaluate(&self, input: &[f64], output: &mut [f64]) -> Result<(), NeatError> {
+        STATE.with(|state| {
+            let state = &mut state.borrow_mut();
+            state.prepare(self.buffer_len());
+            self.evaluate_with_state(state, input, output)
+        })
+    }
+
+    // Wertet das Netzwerk für input.len() / input_count viele Eingaben nacheinander aus. Die Ein- und Ausgaben
+    // liegen hintereinander in input bzw. output. Rekurrente Netzwerke behalten ihren Zustand zwischen den
+    // Eingaben, die Eingaben bilden also eine Zeitreihe. Jeder Aufruf beginnt mit einem frischen Zustand.
+    pub fn evaluate_batch(&self, inputs: &[f64], outputs: &mut [f64]) -> Result<(), NeatError> {
+        // Die Anzahl der Auswertungen ergibt sich aus outputs, inputs muss genau so viele Eingaben enthalten
+        let samples = outputs.len() / self.output_count().max(1);
+        if outputs.len() != samples * self.output_count() {
+            return Err(NeatError::OutputLength {
+                expected: samples * self.output_count(),
+                actual: outputs.len(),
+            });
+        }
+        if inputs.len() != samples * self.input_count {
+            return Err(NeatError::InputLength {
+                expected: samples * self.input_count,
+                actual: inputs.len(),
+            });
+        }
+        STATE.with(|state| {
+            let state = &mut state.borrow_mut();
+            state.prepare(self.buffer_len());
+            inputs
+                .chunks(self.input_count.max(1))
+                .zip(outputs.chunks_mut(self.output_count().max(1)))
+                .try_for_each(|(input, output)| self.evaluate_with_state(state, input, output))
+        })
+    }
+
+    pub fn evaluate_with_state(
+        &self,
+        state: &mut NetworkState,
+        input: &[f64],
+        output: &mut [f64],
+    ) -> Result<(), NeatError> {
+        if input.len() != self.input_count {
+            return Err(NeatError::InputLength {
+                expected: self.input_count,
+                actual: input.len(),
+            });
+        }
+        if output.len() != self.outputs.len() {
+            return Err(NeatError::OutputLength {
+                expected: self.outputs.len(),
+                actual: output.len(),
+            });
+        }
+        if state.values.len() != self.buffer_len() {
+            state.prepare(self.buffer_len());
+        }
+
+        match self.network_type {
+            NetworkType::FeedForward => {
+                state.values[..self.input_count].copy_from_slice(input);
+                self.evaluate_pass(state);
+            }
+            NetworkType::Timeline => {
+                state.previous.copy_from_slice(&state.values);
+                state.values[..self.input_count].copy_from_slice(input);
+                self.evaluate_pass(state);
+            }
+            NetworkType::Stabilizing {
+                max_iterations,
+                tolerance,
+            } => {
+                // Im ersten Durchlauf liefern Rückwärtskanten 0
+                state.reset();
+                state.values[..self.input_count].copy_from_slice(input);
+                self.evaluate_pass(state);
+                for _ in 1..max_iterations {
+                    state.previous.copy_from_slice(&state.values);
+                    self.evaluate_pass(state);
+                    if state
+                        .values
+                        .iter()
+                        .zip(&state.previous)
+                        .all(|(value, previous)| (value - previous).abs() <= tolerance)
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+
+        for (value, index) in output.iter_mut().zip(&self.outputs) {
+            *value = state.values[*index];
+        }
+        Ok(())
+    }
+
+    fn evaluate_pass(&self, state: &mut NetworkState) {
+        let mut edges_start = 0;
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut value = node.bias;
+            for edge in &self.edges[edges_start..node.edges_end] {
+                if edge.recurrent {
+                    value += edge.weight * state.previous[edge.from];
+                } else {
+                    value += edge.weight * state.values[edge.from];
+                }
+            }
+            edges_start = node.edges_end;
+            state.values[self.input_count + i] = (node.activation)(value);
+        }
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.input_count + self.nodes.len()
+    }
+}
+
+impl NetworkState {
+    pub fn reset(&mut self) {
+        self.values.iter_mut().for_each(|value| *value = 0.0);
+        self.previous.iter_mut().for_each(|value| *value = 0.0);
+    }
+
+    // Passt die Größe der Buffer an (alloziert nur, falls die Buffer wachsen müssen) und setzt sie zurück
+    fn prepare(&mut self, len: usize) {
+        self.values.resize(len, 0.0);
+        self.previous.resize(len, 0.0);
+        self.reset();
+    }
+}
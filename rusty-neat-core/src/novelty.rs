@@ -0,0 +1,165 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use rusty_neat_interchange::novelty::PrintableNoveltyArchive;
+
+use crate::{
+    config::{Validate, Validation},
+    organism::Organism,
+};
+
+// Archiv der Verhaltensweisen (behaviour) besonders neuartiger Organismen früherer Generationen.
+// Die Novelty eines Organismus ist der mittlere Abstand seines Verhaltens zu den k nächsten Verhaltensweisen
+// der aktuellen Generation und des Archivs.
+#[derive(Clone, Debug, Default)]
+pub struct NoveltyArchive {
+    behaviours: Vec<Vec<f64>>, // Der neueste Eintrag zuletzt
+}
+
+impl NoveltyArchive {
+    pub fn new() -> Self {
+        NoveltyArchive::default()
+    }
+
+    pub fn behaviours(&self) -> &[Vec<f64>] {
+        &self.behaviours
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviours.is_empty()
+    }
+
+    // Berechnet die Novelty aller Organismen und fügt anschließend neue Verhaltensweisen in das Archiv ein
+    pub(crate) fn update<R: Rng>(
+        &mut self,
+        organisms: &mut [Organism],
+        config: &NoveltyConfig,
+        rng: &mut R,
+    ) {
+        let novelty: Vec<f64> = (0..organisms.len())
+            .map(|i| self.novelty(i, organisms, config.nearest_neighbours))
+            .collect();
+        for (organism, novelty) in organisms.iter_mut().zip(&novelty) {
+            organism.set_novelty(*novelty, config.fitness_weight);
+        }
+
+        match config.archive_insertion {
+            ArchiveInsertion::Threshold { threshold } => {
+                for (organism, novelty) in organisms.iter().zip(&novelty) {
+                    if *novelty > threshold {
+                        self.insert(organism);
+                    }
+                }
+            }
+            ArchiveInsertion::Random { probability } => {
+                for organism in organisms.iter() {
+                    if rng.gen_bool(probability) {
+                        self.insert(organism);
+                    }
+                }
+            }
+            ArchiveInsertion::MostNovel { count } => {
+                let mut order: Vec<usize> = (0..organisms.len()).collect();
+                order.sort_by(|a, b| novelty[*b].total_cmp(&novelty[*a]));
+                for index in order.into_iter().take(count) {
+                    self.insert(&organisms[index]);
+                }
+            }
+        }
+
+        // Die ältesten Einträge entfernen
+        if config.max_archive_size != 0 && self.behaviours.len() > config.max_archive_size {
+            let excess = self.behaviours.len() - config.max_archive_size;
+            self.behaviours.drain(..excess);
+        }
+    }
+
+    fn novelty(&self, index: usize, organisms: &[Organism], k: usize) -> f64 {
+        let behaviour = organisms[index].behaviour.as_ref().unwrap();
+        let mut distances: Vec<f64> = organisms
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, other)| other.behaviour.as_ref().unwrap())
+            .chain(&self.behaviours)
+            .map(|other| distance(behaviour, other))
+            .collect();
+        if distances.is_empty() {
+            return 0.0;
+        }
+
+        let k = k.min(distances.len());
+        distances.select_nth_unstable_by(k - 1, |a, b| a.total_cmp(b));
+        distances[..k].iter().sum::<f64>() / k as f64
+    }
+
+    fn insert(&mut self, organism: &Organism) {
+        self.behaviours
+            .push(organism.behaviour.as_ref().unwrap().clone());
+    }
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+impl From<&NoveltyArchive> for PrintableNoveltyArchive {
+    fn from(archive: &NoveltyArchive) -> Self {
+        PrintableNoveltyArchive {
+            behaviours: archive.behaviours.clone(),
+        }
+    }
+}
+
+impl From<PrintableNoveltyArchive> for NoveltyArchive {
+    fn from(printable: PrintableNoveltyArchive) -> Self {
+        NoveltyArchive {
+            behaviours: printable.behaviours,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoveltyConfig {
+    pub nearest_neighbours: usize, // Anzahl der nächsten Nachbarn (k), aus denen die Novelty berechnet wird
+    pub fitness_weight: f64, // Anteil der Fitness an der Bewertung, der Rest ist Novelty (0 entspricht reiner Novelty Search)
+    pub archive_insertion: ArchiveInsertion, // Welche Verhaltensweisen in das Archiv aufgenommen werden
+    pub max_archive_size: usize, // Maximale Größe des Archivs, die ältesten Einträge werden entfernt (0 entspricht unbegrenzt)
+}
+
+impl Default for NoveltyConfig {
+    fn default() -> Self {
+        NoveltyConfig {
+            nearest_neighbours: 15,
+            fitness_weight: 0.0,
+            archive_insertion: ArchiveInsertion::Random { probability: 0.01 },
+            max_archive_size: 0,
+        }
+    }
+}
+
+impl Validate for NoveltyConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.not_zero(self.nearest_neighbours, "nearest_neighbours");
+        validation.ratio(self.fitness_weight, "fitness_weight");
+        if let ArchiveInsertion::Random { probability } = self.archive_insertion {
+            validation.probability(probability, "archive_insertion.probability");
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum ArchiveInsertion {
+    Threshold { threshold: f64 }, // Alle Organismen mit einer Novelty über threshold
+    Random { probability: f64 },  // Jeder Organismus mit der Wahrscheinlichkeit probability
+    MostNovel { count: usize },   // Die count Organismen mit der höchsten Novelty
+}
@@ -0,0 +1,201 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use crate::{error::NeatError, organism::Organism, reproduction::Phase};
+
+// Wird von Population::evolve über den Verlauf der Evolution informiert. Alle Methoden haben eine leere
+// Standardimplementierung, ein Observer muss also nur die Ereignisse implementieren, die ihn interessieren.
+pub trait EvolutionObserver {
+    fn on_generation_start(
+        &mut self,
+        _generation: u32,
+        _organism_count: usize,
+        _species_count: usize,
+    ) {
+    }
+
+    // Wird aufgerufen, nachdem die Organismen der Generation bewertet und in Spezies eingeteilt wurden.
+    // Gibt ein Observer EvolutionControl::Stop zurück, wird die Evolution nach dieser Generation beendet.
+    fn on_generation_end(&mut self, _statistics: &GenerationStatistics) -> EvolutionControl {
+        EvolutionControl::Continue
+    }
+
+    fn on_best_organism(&mut self, _generation: u32, _organism: &Organism) {}
+
+    fn on_species_created(&mut self, _generation: u32, _species_id: usize) {}
+
+    fn on_species_extinct(&mut self, _generation: u32, _species_id: usize) {}
+
+    // Die Spezies wird wegen Stagnation entfernt, anschließend folgt on_species_extinct
+    fn on_species_stagnated(
+        &mut self,
+        _generation: u32,
+        _species_id: usize,
+        _stagnant_generations: u32,
+    ) {
+    }
+
+    // Die folgenden Generationen werden vereinfacht bzw. wieder komplexifiziert
+    fn on_phase_changed(&mut self, _generation: u32, _phase: Phase) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvolutionControl {
+    Continue,
+    Stop,
+}
+
+#[derive(Clone, Debug)]
+pub struct GenerationStatistics {
+    pub generation: u32,
+    pub organism_count: usize,
+    pub species_count: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+    pub best_genome_id: u64,
+    pub best_node_count: usize,
+    pub best_connection_count: usize,
+    pub best_enabled_connection_count: usize,
+    pub pool_node_count: usize,
+    pub pool_connection_count: usize,
+}
+
+// Ignoriert alle Ereignisse
+pub struct SilentObserver;
+
+impl EvolutionObserver for SilentObserver {}
+
+// Gibt den Fortschritt auf stdout aus
+pub struct StdoutObserver;
+
+impl EvolutionObserver for StdoutObserver {
+    fn on_generation_start(
+        &mut self,
+        generation: u32,
+        organism_count: usize,
+        species_count: usize,
+    ) {
+        println!(
+            "Generation {}: {} organisms, {} species",
+            generation, organism_count, species_count
+        );
+    }
+
+    fn on_generation_end(&mut self, statistics: &GenerationStatistics) -> EvolutionControl {
+        println!(
+            "=> Best genome {}, fitness {:.10} using {} nodes and {} connections ({} enabled)",
+            statistics.best_genome_id,
+            statistics.best_fitness,
+            statistics.best_node_count,
+            statistics.best_connection_count,
+            statistics.best_enabled_connection_count
+        );
+        println!(
+            "=> Gene Pool: {} nodes, {} connections\n",
+            statistics.pool_node_count, statistics.pool_connection_count
+        );
+        EvolutionControl::Continue
+    }
+
+    fn on_species_created(&mut self, _generation: u32, species_id: usize) {
+        println!("Creating a new species ({})", species_id);
+    }
+
+    fn on_species_extinct(&mut self, _generation: u32, species_id: usize) {
+        println!("Species {} died out", species_id);
+    }
+
+    fn on_species_stagnated(
+        &mut self,
+        _generation: u32,
+        species_id: usize,
+        stagnant_generations: u32,
+    ) {
+        println!(
+            "Species {} stagnated for {} generations",
+            species_id, stagnant_generations
+        );
+    }
+
+    fn on_phase_changed(&mut self, _generation: u32, phase: Phase) {
+        match phase {
+            Phase::Simplifying => println!("Starting a simplification phase"),
+            Phase::Complexifying => println!("Starting a complexification phase"),
+        }
+    }
+}
+
+// Schreibt die GenerationStatistics jeder Generation als Zeile im CSV Format. Schlägt das Schreiben fehl,
+// wird die Evolution beendet und der Fehler über error bereitgestellt.
+pub struct CsvObserver<W: Write> {
+    writer: W,
+    header_written: bool,
+    error: Arc<Mutex<Option<io::Error>>>,
+}
+
+impl CsvObserver<BufWriter<File>> {
+    pub fn create(path: &Path) -> Result<Self, NeatError> {
+        let file = File::create(path).map_err(|err| NeatError::io(path.to_owned(), err))?;
+        Ok(CsvObserver::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> CsvObserver<W> {
+    pub fn new(writer: W) -> Self {
+        CsvObserver {
+            writer,
+            header_written: false,
+            error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    // Der Fehler, an dem das Schreiben gescheitert ist. Da der Observer mit add_observer an die Population
+    // übergeben wird, muss der Zugriff vorher geholt werden.
+    pub fn error(&self) -> Arc<Mutex<Option<io::Error>>> {
+        Arc::clone(&self.error)
+    }
+
+    fn write_row(&mut self, statistics: &GenerationStatistics) -> io::Result<()> {
+        if !self.header_written {
+            writeln!(
+                self.writer,
+                "generation,organism_count,species_count,best_fitness,mean_fitness,best_genome_id,\
+                best_node_count,best_connection_count,best_enabled_connection_count,\
+                pool_node_count,pool_connection_count"
+            )?;
+            self.header_written = true;
+        }
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            statistics.generation,
+            statistics.organism_count,
+            statistics.species_count,
+            statistics.best_fitness,
+            statistics.mean_fitness,
+            statistics.best_genome_id,
+            statistics.best_node_count,
+            statistics.best_connection_count,
+            statistics.best_enabled_connection_count,
+            statistics.pool_node_count,
+            statistics.pool_connection_count
+        )?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> EvolutionObserver for CsvObserver<W> {
+    fn on_generation_end(&mut self, statistics: &GenerationStatistics) -> EvolutionControl {
+        match self.write_row(statistics) {
+            Ok(()) => EvolutionControl::Continue,
+            Err(err) => {
+                *self.error.lock().unwrap() = Some(err);
+                EvolutionControl::Stop
+            }
+        }
+    }
+}
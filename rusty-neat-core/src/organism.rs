@@ -1,20 +1,29 @@
 use std::{
+    cmp::Ordering,
     fmt,
-    sync::{Arc},
+    sync::{Arc, Mutex, OnceLock},
 };
 
 use rusty_neat_interchange::organism::PrintableOrganism;
 
 use crate::{
+    error::NeatError,
     gene_pool::GenePool,
     genome::{DistanceConfig, EvaluationConfig, Genome},
+    network::{Network, NetworkState},
 };
 
-#[derive(Clone)]
 pub struct Organism {
     pub(crate) genome: Genome,
     evaluation_config: Arc<EvaluationConfig>,
     pub fitness: Option<f64>,
+    pub behaviour: Option<Vec<f64>>, // Beschreibung des Verhaltens für die Novelty Search
+    novelty: Option<f64>,
+    pub objectives: Option<Vec<f64>>, // Werte der Ziele bei der Multi-Objective Evolution
+    pareto_rank: Option<usize>,       // Index der Pareto-Front (0 ist die beste Front)
+    selection_fitness: Option<f64>, // Aus Novelty und Fitness bzw. den Zielen berechnete Bewertung für die Reproduktion
+    network: OnceLock<Network>,     // Wird bei der ersten Auswertung aus dem Genome kompiliert
+    state: Mutex<NetworkState>,     // Zustand rekurrenter Netzwerke zwischen zwei Auswertungen
 }
 
 impl Organism {
@@ -23,6 +32,13 @@ impl Organism {
             genome,
             evaluation_config,
             fitness: None,
+            behaviour: None,
+            novelty: None,
+            objectives: None,
+            pareto_rank: None,
+            selection_fitness: None,
+            network: OnceLock::new(),
+            state: Mutex::new(NetworkState::default()),
         }
     }
 
@@ -31,15 +47,84 @@ impl Organism {
         pool: &GenePool,
         evaluation_config: Arc<EvaluationConfig>,
     ) -> Self {
-        Organism {
-            genome: Genome::from_printable(&printable.genome, pool),
+        let mut organism = Organism::new(
+            Genome::from_printable(&printable.genome, pool),
             evaluation_config,
-            fitness: printable.fitness,
-        }
+        );
+        organism.fitness = printable.fitness;
+        organism.behaviour = printable.behaviour.clone();
+        organism.novelty = printable.novelty;
+        organism.objectives = printable.objectives.clone();
+        organism.pareto_rank = printable.pareto_rank;
+        organism
+    }
+
+    pub fn genome(&self) -> &Genome {
+        &self.genome
+    }
+
+    pub fn novelty(&self) -> Option<f64> {
+        self.novelty
+    }
+
+    pub fn pareto_rank(&self) -> Option<usize> {
+        self.pareto_rank
+    }
+
+    // Die Bewertung, nach der die Organismen reproduziert werden. Ohne Novelty Search ist das die Fitness.
+    pub fn selection_fitness(&self) -> Option<f64> {
+        self.selection_fitness.or(self.fitness)
+    }
+
+    pub(crate) fn set_novelty(&mut self, novelty: f64, fitness_weight: f64) {
+        self.novelty = Some(novelty);
+        self.selection_fitness =
+            Some((1.0 - fitness_weight) * novelty + fitness_weight * self.fitness.unwrap_or(0.0));
+    }
+
+    pub(crate) fn set_pareto_rank(&mut self, rank: usize, selection_fitness: f64) {
+        self.pareto_rank = Some(rank);
+        self.selection_fitness = Some(selection_fitness);
+    }
+
+    // Benötigt nur &self, sodass mehrere Organismen parallel bewertet werden können. Rekurrente Netzwerke
+    // (NetworkType::Timeline) behalten ihren Zustand zwischen zwei Aufrufen bis reset_state aufgerufen wird.
+    pub fn evaluate(&self, input: &[f64]) -> Result<Vec<f64>, NeatError> {
+        let mut output = vec![0.0; self.compiled_network().output_count()];
+        self.evaluate_into(input, &mut output)?;
+        Ok(output)
+    }
+
+    // Wie evaluate, schreibt die Outputs aber ohne Allokation in output
+    pub fn evaluate_into(&self, input: &[f64], output: &mut [f64]) -> Result<(), NeatError> {
+        self.compiled_network()
+            .evaluate_with_state(&mut self.state.lock().unwrap(), input, output)
+    }
+
+    // Das kompilierte Netzwerk, das ohne &mut self (und von mehreren Threads) ausgewertet werden kann
+    pub fn network(&self) -> &Network {
+        self.compiled_network()
+    }
+
+    pub fn reset_state(&mut self) {
+        self.state.get_mut().unwrap().reset();
     }
 
-    pub fn evaluate(&mut self, input: &[f64]) -> Vec<f64> {
-        self.genome.evaluate(input, self.evaluation_config.as_ref())
+    // Muss für jede Änderung am Genome verwendet werden, damit das Netzwerk neu kompiliert wird
+    pub(crate) fn genome_mut(&mut self) -> &mut Genome {
+        self.behaviour = None;
+        self.novelty = None;
+        self.objectives = None;
+        self.pareto_rank = None;
+        self.selection_fitness = None;
+        self.network = OnceLock::new();
+        *self.state.get_mut().unwrap() = NetworkState::default();
+        &mut self.genome
+    }
+
+    fn compiled_network(&self) -> &Network {
+        self.network
+            .get_or_init(|| self.genome.compile(self.evaluation_config.as_ref()))
     }
 
     pub(crate) fn distance(&self, other: &Organism, config: Arc<DistanceConfig>) -> f64 {
@@ -47,43 +132,64 @@ impl Organism {
     }
 }
 
-impl Into<PrintableOrganism> for &Organism {
-    fn into(self) -> PrintableOrganism {
+impl From<&Organism> for PrintableOrganism {
+    fn from(organism: &Organism) -> Self {
         PrintableOrganism {
-            genome: (&self.genome).into(),
+            genome: (&organism.genome).into(),
+            fitness: organism.fitness,
+            behaviour: organism.behaviour.clone(),
+            novelty: organism.novelty,
+            objectives: organism.objectives.clone(),
+            pareto_rank: organism.pareto_rank,
+        }
+    }
+}
+
+// Die Kopie beginnt mit einem zurückgesetzten Zustand
+impl Clone for Organism {
+    fn clone(&self) -> Self {
+        Organism {
+            genome: self.genome.clone(),
+            evaluation_config: Arc::clone(&self.evaluation_config),
             fitness: self.fitness,
+            behaviour: self.behaviour.clone(),
+            novelty: self.novelty,
+            objectives: self.objectives.clone(),
+            pareto_rank: self.pareto_rank,
+            selection_fitness: self.selection_fitness,
+            network: self.network.clone(),
+            state: Mutex::new(NetworkState::default()),
         }
     }
 }
 
+// Muss mit Ord übereinstimmen (auch für NaN und -0.0)
 impl PartialEq for Organism {
     fn eq(&self, other: &Self) -> bool {
-        if self.fitness.is_some() && other.fitness.is_some() {
-            self.fitness.unwrap().eq(&other.fitness.unwrap())
-        } else {
-            false
-        }
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for Organism {}
 
 impl PartialOrd for Organism {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if self.fitness.is_some() && other.fitness.is_some() {
-            self.fitness.unwrap().partial_cmp(&other.fitness.unwrap())
-        } else {
-            None
-        }
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Organism {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.fitness
-            .unwrap()
-            .partial_cmp(&other.fitness.unwrap())
-            .unwrap()
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_fitness(self.selection_fitness(), other.selection_fitness())
+    }
+}
+
+// Organismen ohne Fitness sind schlechter als alle bewerteten Organismen. Verwendet total_cmp, damit auch
+// eine ungültige Fitness (NaN) nicht zu einem Panic führt.
+pub(crate) fn compare_fitness(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
     }
 }
 
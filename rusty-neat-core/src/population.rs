@@ -1,134 +1,494 @@
 use core::f64;
+use hashbrown::HashMap;
+use rand::{rngs::StdRng, SeedableRng};
+use rayon::{
+    iter::{IntoParallelRefMutIterator, ParallelIterator},
+    ThreadPoolBuilder,
+};
 use serde::{Deserialize, Serialize};
 use std::{
+    cmp::min,
     fs,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 use rusty_neat_interchange::{
-    generation::{self, PrintableGeneration},
+    generation::{self, MigrationDefaults, PrintableGeneration},
     io::FileType,
     neat_result::{self, PrintableNeatResult},
+    novelty,
 };
 
 use crate::{
-    config_util::assert_not_negative,
-    gene_pool::GenePool,
+    config::{ConfigLoader, Validate, Validation},
+    error::{ConfigIssue, NeatError},
+    gene_pool::{GenePool, InitialTopology},
     genome::{DistanceConfig, EvaluationConfig, GenomeIdGenerator, NewConnectionWeight},
-    organism::Organism,
-    reproduction::{self, ReproductionConfig},
+    multi_objective::{self, MultiObjectiveConfig},
+    novelty::{NoveltyArchive, NoveltyConfig},
+    observer::{EvolutionControl, EvolutionObserver, GenerationStatistics},
+    organism::{compare_fitness, Organism},
+    reproduction::{self, Phase, PhaseState, ReproductionConfig},
     species::{Species, SpeciesConfig},
 };
 
 pub struct Population {
     config: Arc<PopulationConfig>,
     pub(crate) pool: GenePool,
-    pub(crate) organisms: Vec<Arc<Organism>>,
+    organisms: Vec<Organism>, // Die Organismen der aktuellen Generation
     pub(crate) species: Vec<Species>,
     next_species_id: usize,
     pub(crate) genome_id_generator: GenomeIdGenerator,
+    generation: u32,         // Die aktuelle Generation
+    completed: bool,         // Ob die aktuelle Generation bewertet und in Spezies eingeteilt wurde
+    distance_tolerance: f64, // Aktuelle Toleranz für die Zugehörigkeit zu einer Spezies
+    rng: StdRng,
+    observers: Vec<Box<dyn EvolutionObserver>>,
+    output: Option<(PathBuf, FileType)>, // Wohin die Generationen geschrieben werden
+    archive: NoveltyArchive,
+    pub(crate) phase: PhaseState,
+    config_warnings: Vec<ConfigIssue>, // Warnungen der Prüfung der Konfiguration
 }
 
 impl Population {
-    pub fn new(pool: GenePool, config_path: &Path) -> Result<Population, String> {
-        let config: PopulationConfig =
-            serde_json::from_str(&fs::read_to_string(config_path).map_err(|e| e.to_string())?)
-                .map_err(|e| e.to_string())?;
-        if let Err(msg) = config.validate() {
-            return Err(msg);
-        }
+    // Liest die Konfiguration aus config_path (JSON, TOML oder YAML). Für mehrere Schichten und Überschreibungen
+    // aus der Umgebung siehe ConfigLoader und with_config.
+    pub fn new(pool: GenePool, config_path: &Path) -> Result<Population, NeatError> {
+        Population::with_config(pool, ConfigLoader::new().file(config_path)?.load()?)
+    }
+
+    pub fn with_config(
+        mut pool: GenePool,
+        config: PopulationConfig,
+    ) -> Result<Population, NeatError> {
+        let config_warnings = config.validate()?;
+        configure_pool(&mut pool, &config);
+        pool.create_initial_hidden_nodes(config.initial_topology.hidden_nodes);
         let population = Population {
-            pool: pool,
+            pool,
             organisms: Vec::with_capacity(config.reproduction.organism_count),
+            rng: generation_rng(config.seed, 0),
+            distance_tolerance: config.species.species_distance_tolerance,
             config: Arc::from(config),
             species: Vec::with_capacity(1),
             next_species_id: 0,
             genome_id_generator: GenomeIdGenerator::new(),
+            generation: 0,
+            completed: false,
+            observers: Vec::new(),
+            output: None,
+            archive: NoveltyArchive::new(),
+            phase: PhaseState::new(),
+            config_warnings,
         };
         Ok(population)
     }
 
-    pub fn evolve<F: Fn(&mut [Organism]) -> ()>(
+    // Stellt die Population aus der letzten Generation (gen-N.*) in target_path wieder her.
+    // Ein anschließender Aufruf von next_generation bzw. evolve setzt die Evolution mit Generation N + 1 fort.
+    pub fn resume(config_path: &Path, target_path: &Path) -> Result<Population, NeatError> {
+        Population::resume_with_config(ConfigLoader::new().file(config_path)?.load()?, target_path)
+    }
+
+    pub fn resume_with_config(
+        config: PopulationConfig,
+        target_path: &Path,
+    ) -> Result<Population, NeatError> {
+        let config_warnings = config.validate()?;
+        let latest = find_latest_generation(target_path)?;
+        // Dateien der Version 0 verwenden für alle Nodes die Aktivierungsfunktion und den Bias der Konfiguration
+        let defaults = MigrationDefaults {
+            activation: (&config.evaluation.activation).into(),
+            bias: config.evaluation.bias,
+        };
+        let printable: PrintableGeneration = generation::read_migrated(&latest, &defaults)?;
+
+        let mut pool = GenePool::from_printable(&printable.pool);
+        configure_pool(&mut pool, &config);
+
+        let mut species: Vec<Species> = printable
+            .species
+            .iter()
+            .map(|s| {
+                Species::from_printable(
+                    s,
+                    &pool,
+                    Arc::clone(&config.species),
+                    Arc::clone(&config.evaluation),
+                )
+            })
+            .collect();
+
+        // Ältere Dateien enthalten nur die Organismen der Spezies
+        let mut organisms: Vec<Organism> = if printable.organisms.is_empty() {
+            species
+                .iter()
+                .flat_map(|s| s.organisms.iter().map(|o| (**o).clone()))
+                .collect()
+        } else {
+            printable
+                .organisms
+                .iter()
+                .map(|o| Organism::from_printable(o, &pool, Arc::clone(&config.evaluation)))
+                .collect()
+        };
+
+        // Das Archiv der Novelty Search liegt als archive-N.* neben gen-N.*
+        let mut archive = NoveltyArchive::new();
+        if let Some(novelty) = &config.novelty {
+            let archive_path = latest.with_file_name(
+                latest
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .replacen("gen-", "archive-", 1),
+            );
+            if archive_path.exists() {
+                archive = novelty::read(&archive_path)?;
+            }
+            for organism in organisms.iter_mut() {
+                if let Some(value) = organism.novelty() {
+                    organism.set_novelty(value, novelty.fitness_weight);
+                }
+            }
+        }
+
+        // Die Pareto-Fronten werden aus den gespeicherten Zielen aller Organismen neu berechnet
+        if let Some(multi_objective) = &config.multi_objective {
+            multi_objective::rank(&mut organisms, multi_objective);
+        }
+
+        // Die Organismen der Spezies erhalten die wiederhergestellte Bewertung für die Reproduktion
+        if config.novelty.is_some() || config.multi_objective.is_some() {
+            let restored: HashMap<u64, &Organism> =
+                organisms.iter().map(|o| (o.genome().id(), o)).collect();
+            for organism in species.iter_mut().flat_map(|s| s.organisms.iter_mut()) {
+                if let Some(restored) = restored.get(&organism.genome().id()) {
+                    *organism = Arc::new((*restored).clone());
+                }
+            }
+        }
+
+        // Ältere Dateien enthalten weder die nächsten Ids noch die Toleranz
+        let next_species_id = species
+            .iter()
+            .map(|s| s.id() + 1)
+            .chain([printable.next_species_id])
+            .max()
+            .unwrap();
+        let last_genome_id = organisms
+            .iter()
+            .map(|o| o.genome().id())
+            .chain([printable.last_genome_id])
+            .max()
+            .unwrap();
+        let distance_tolerance = printable
+            .distance_tolerance
+            .unwrap_or(config.species.species_distance_tolerance);
+
+        Ok(Population {
+            pool,
+            organisms,
+            rng: generation_rng(config.seed, printable.generation),
+            distance_tolerance,
+            config: Arc::from(config),
+            species,
+            next_species_id,
+            genome_id_generator: GenomeIdGenerator::starting_after(last_genome_id),
+            generation: printable.generation,
+            completed: true,
+            observers: Vec::new(),
+            output: None,
+            archive,
+            phase: (&printable.phase).into(),
+            config_warnings,
+        })
+    }
+
+    // Ohne Observer verläuft die Evolution ohne jede Ausgabe
+    pub fn add_observer<O: EvolutionObserver + 'static>(&mut self, observer: O) {
+        self.observers.push(Box::new(observer));
+    }
+
+    // Ab jetzt wird jede abgeschlossene Generation als gen-N.* und am Ende von evolve das Ergebnis nach
+    // target_path geschrieben. Bei einer neuen Population wird der Inhalt von target_path vorher gelöscht.
+    pub fn write_generations(
         &mut self,
-        fitness_function: F,
         target_path: &Path,
-    ) -> Result<Organism, String> {
-        prepare_target_directory(target_path)?;
+        file_type: FileType,
+    ) -> Result<(), NeatError> {
+        if self.organisms.is_empty() {
+            prepare_target_directory(target_path)?;
+        } else {
+            fs::create_dir_all(target_path)
+                .map_err(|err| NeatError::io(target_path.to_owned(), err))?;
+        }
+        self.output = Some((target_path.to_owned(), file_type));
+        Ok(())
+    }
 
-        self.generate_initial_population(&fitness_function);
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
 
-        let target_fitness = self.config.target_fitness;
-        let mut best_organism = Arc::clone(&self.organisms[0]);
+    pub fn config(&self) -> &PopulationConfig {
+        &self.config
+    }
 
-        let max_generations = self.config.max_generations;
-        let mut generation = 1;
+    pub fn pool(&self) -> &GenePool {
+        &self.pool
+    }
+
+    // Erlaubte, aber vermutlich ungewollte Einstellungen der Konfiguration (z.B. eine Toleranz von 0)
+    pub fn config_warnings(&self) -> &[ConfigIssue] {
+        &self.config_warnings
+    }
+
+    pub fn organisms(&self) -> &[Organism] {
+        &self.organisms
+    }
+
+    // Die Organismen der aktuellen Generation, damit der Benutzer sie bewerten kann
+    pub fn organisms_mut(&mut self) -> &mut [Organism] {
+        &mut self.organisms
+    }
+
+    // Die Spezies der zuletzt abgeschlossenen Generation
+    pub fn species(&self) -> &[Species] {
+        &self.species
+    }
+
+    pub fn archive(&self) -> &NoveltyArchive {
+        &self.archive
+    }
+
+    // Ob die nächste Generation komplexifiziert oder vereinfacht wird (siehe ReproductionConfig::simplification)
+    pub fn phase(&self) -> Phase {
+        self.phase.phase()
+    }
+
+    // Die Toleranz, mit der die nächste Generation in Spezies eingeteilt wird
+    pub fn distance_tolerance(&self) -> f64 {
+        self.distance_tolerance
+    }
+
+    // Der beste bereits bewertete Organismus der aktuellen Generation
+    pub fn best(&self) -> Option<&Organism> {
+        self.organisms
+            .iter()
+            .filter(|o| o.fitness.is_some())
+            .max_by(|x, y| x.fitness.unwrap().total_cmp(&y.fitness.unwrap()))
+    }
+
+    // Erzeugt die nächste Generation. Beim ersten Aufruf wird die initiale Population (Generation 0) erzeugt,
+    // danach wird die aktuelle Generation zuerst mit finish_generation abgeschlossen (falls das noch nicht
+    // geschehen ist) und anschließend reproduziert. Die neuen Organismen müssen vor dem nächsten Aufruf über
+    // organisms_mut bewertet werden. Verlangt ein Observer das Ende der Evolution, wird keine neue Generation
+    // erzeugt und EvolutionControl::Stop zurückgegeben.
+    pub fn next_generation(&mut self) -> Result<EvolutionControl, NeatError> {
+        if self.organisms.is_empty() {
+            self.generate_initial_population();
+        } else {
+            if !self.completed && self.finish_generation()? == EvolutionControl::Stop {
+                return Ok(EvolutionControl::Stop);
+            }
+
+            let generation = self.generation + 1;
+            let mut rng = generation_rng(self.config.seed, generation);
 
-        while generation <= max_generations && best_organism.fitness.unwrap() <= target_fitness {
-            println!(
-                "Generation {}: {} organisms, {} species",
-                generation,
-                self.organisms.len(),
-                self.species.len()
-            );
             // Neue Organismen erzeugen (durch Elitismus, Kreuzung und Mutationen)
-            println!("Reproducing...");
-            let mut new_organisms = reproduction::reproduce(
+            self.organisms = reproduction::reproduce(
                 self,
                 Arc::clone(&self.config.reproduction),
                 Arc::clone(&self.config.evaluation),
+                Arc::clone(&self.config.distance),
                 generation,
+                &mut rng,
             );
+            self.rng = rng;
+            self.generation = generation;
+            self.completed = false;
+        }
 
-            // Der Benutzer bewertet die Organismen
-            println!("Evaluating...");
-            fitness_function(&mut new_organisms);
+        let (generation, organism_count, species_count) =
+            (self.generation, self.organisms.len(), self.species.len());
+        self.observers.iter_mut().for_each(|observer| {
+            observer.on_generation_start(generation, organism_count, species_count)
+        });
+        Ok(EvolutionControl::Continue)
+    }
 
-            // TODO: Cloning vermeiden
-            println!("Copying organisms...");
-            self.organisms = new_organisms.iter().map(|x| Arc::from(x.clone())).collect();
+    // Schließt die bewertete aktuelle Generation ab: Die Organismen werden in Spezies eingeteilt, die Observer
+    // informiert und die Generation geschrieben (siehe write_generations).
+    pub fn finish_generation(&mut self) -> Result<EvolutionControl, NeatError> {
+        if self.completed {
+            return Err(NeatError::InvalidState(format!(
+                "generation {} is already finished",
+                self.generation
+            )));
+        }
+        if self.organisms.is_empty() {
+            return Err(NeatError::InvalidState(
+                "the population has no organisms yet, call next_generation first".to_owned(),
+            ));
+        }
+        if self.organisms.iter().any(|o| o.fitness.is_none()) {
+            return Err(NeatError::MissingFitness);
+        }
+        if self
+            .organisms
+            .iter()
+            .any(|o| !o.fitness.unwrap().is_finite())
+        {
+            return Err(NeatError::NotFinite("fitness"));
+        }
 
-            // Beste erreichte Fitness für die Abbruchbedingung ermitteln
-            best_organism = Arc::clone(
-                self.organisms
-                    .iter()
-                    .max_by(|x, y| x.fitness.unwrap().partial_cmp(&y.fitness.unwrap()).unwrap())
-                    .unwrap(),
-            );
-            println!(
-                "=> Best genome {}, fitness {:.10} using {} nodes and {} connections ({} enabled)",
-                best_organism.genome.id(),
-                best_organism.fitness.unwrap(),
-                best_organism.genome.node_count(),
-                best_organism.genome.connection_count(),
-                best_organism.genome.enabled_connection_count()
-            );
-            println!(
-                "=> Gene Pool: {} nodes, {} connections",
-                self.pool.nodes.len(),
-                self.pool.connections.len()
-            );
+        // Novelty Search: Die Reproduktion richtet sich nach der Novelty des Verhaltens (und evtl. der Fitness)
+        if let Some(novelty) = &self.config.novelty {
+            if self.organisms.iter().any(|o| o.behaviour.is_none()) {
+                return Err(NeatError::MissingBehaviour);
+            }
+            if self
+                .organisms
+                .iter()
+                .any(|o| !all_finite(o.behaviour.as_ref()))
+            {
+                return Err(NeatError::NotFinite("behaviour"));
+            }
+            self.archive
+                .update(&mut self.organisms, novelty, &mut self.rng);
+        }
 
-            // Die Organismen in Spezies einteilen
-            println!("Speciating...\n");
-            self.speciate();
+        // Multi-Objective: Die Reproduktion richtet sich nach den Pareto-Fronten der Ziele
+        if let Some(multi_objective) = &self.config.multi_objective {
+            let objective_count = multi_objective.objectives.len();
+            if self.organisms.iter().any(|o| {
+                o.objectives
+                    .as_ref()
+                    .is_none_or(|v| v.len() != objective_count)
+            }) {
+                return Err(NeatError::MissingObjectives(objective_count));
+            }
+            if self
+                .organisms
+                .iter()
+                .any(|o| !all_finite(o.objectives.as_ref()))
+            {
+                return Err(NeatError::NotFinite("objective"));
+            }
+            multi_objective::rank(&mut self.organisms, multi_objective);
+        }
 
-            self.write_generation(generation, target_path, FileType::Bincode)?;
+        let best_organism = self.best().unwrap().clone();
+        let generation = self.generation;
+        self.observers
+            .iter_mut()
+            .for_each(|observer| observer.on_best_organism(generation, &best_organism));
+
+        // Die Organismen in Spezies einteilen
+        self.speciate();
+        self.update_phase();
+        self.completed = true;
 
-            generation += 1;
+        if let Some((path, file_type)) = &self.output {
+            self.write_generation(path, *file_type)?;
         }
 
-        write_result(
-            Arc::clone(&best_organism),
-            &self.pool,
-            target_path,
-            FileType::Bincode,
-        );
+        let statistics = self.statistics(&best_organism);
+        let mut control = EvolutionControl::Continue;
+        for observer in &mut self.observers {
+            if observer.on_generation_end(&statistics) == EvolutionControl::Stop {
+                control = EvolutionControl::Stop;
+            }
+        }
+        Ok(control)
+    }
+
+    // Führt die Evolution durch, bis target_fitness überschritten, max_generations erreicht oder die Evolution
+    // von einem Observer beendet wurde, und gibt den besten Organismus der letzten Generation zurück.
+    pub fn evolve<F: FnMut(&mut [Organism])>(
+        &mut self,
+        mut fitness_function: F,
+    ) -> Result<Organism, NeatError> {
+        if self.organisms.is_empty() {
+            self.next_generation()?;
+        }
+
+        let target_fitness = self.config.target_fitness;
+        let max_generations = self.config.max_generations;
+        loop {
+            // Der Benutzer bewertet die Organismen (eine mit resume wiederhergestellte Generation ist bereits bewertet)
+            if !self.completed {
+                fitness_function(&mut self.organisms);
+            }
 
-        Ok((*best_organism).clone())
+            let best_fitness = self
+                .best()
+                .and_then(|o| o.fitness)
+                .ok_or(NeatError::MissingFitness)?;
+            if best_fitness > target_fitness
+                || (max_generations != 0 && self.generation >= max_generations)
+            {
+                if !self.completed {
+                    self.finish_generation()?;
+                }
+                break;
+            }
+
+            if self.next_generation()? == EvolutionControl::Stop {
+                break;
+            }
+        }
+
+        let best_organism = self.best().unwrap().clone();
+        if let Some((path, file_type)) = &self.output {
+            write_result(&best_organism, &self.pool, path, *file_type)?;
+        }
+        Ok(best_organism)
     }
 
-    fn generate_initial_population<F: Fn(&mut [Organism]) -> ()>(&mut self, fitness_function: F) {
+    // Wie evolve, die Fitness jedes Organismus wird aber einzeln von fitness_function bestimmt. Die Organismen
+    // werden dabei parallel mit evaluation_threads vielen Threads bewertet.
+    pub fn evolve_parallel<F: Fn(&Organism) -> f64 + Sync>(
+        &mut self,
+        fitness_function: F,
+    ) -> Result<Organism, NeatError> {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(self.config.evaluation_threads)
+            .build()
+            .map_err(|err| NeatError::ThreadPool(Box::new(err)))?;
+
+        self.evolve(|organisms| {
+            thread_pool.install(|| {
+                organisms
+                    .par_iter_mut()
+                    .for_each(|organism| organism.fitness = Some(fitness_function(organism)))
+            })
+        })
+    }
+
+    // Überträgt die Einstellungen dieser Population auf ihren (z.B. von IslandModel übergebenen) Gene Pool
+    pub(crate) fn configure_pool(&mut self) {
+        configure_pool(&mut self.pool, &self.config);
+    }
+
+    // Ersetzt zufällige Organismen der neuen, noch nicht bewerteten Generation durch die Genomes der Migranten
+    // (siehe IslandModel). Die Migranten werden in dieser Population neu bewertet. Durch Elitismus übernommene
+    // Organismen (die als einzige bereits eine Fitness haben) werden nicht ersetzt.
+    pub(crate) fn immigrate(&mut self, migrants: Vec<Organism>) {
+        let replaceable: Vec<usize> = (0..self.organisms.len())
+            .filter(|i| self.organisms[*i].fitness.is_none())
+            .collect();
+        let count = min(migrants.len(), replaceable.len());
+        let indices = rand::seq::index::sample(&mut self.rng, replaceable.len(), count);
+        for (index, migrant) in indices.into_iter().zip(migrants) {
+            self.organisms[replaceable[index]] =
+                Organism::new(migrant.genome, Arc::clone(&self.config.evaluation));
+        }
+    }
+
+    fn generate_initial_population(&mut self) {
         let mut organisms: Vec<Organism> =
             Vec::with_capacity(self.config.reproduction.organism_count);
 
@@ -136,17 +496,39 @@ impl Population {
             organisms.push(Organism::new(
                 self.pool.new_genome(
                     &self.config.initial_organism_weight,
+                    &self.config.initial_topology.connections,
                     self.genome_id_generator.next_id(),
                     0,
+                    &mut self.rng,
                 ),
                 Arc::clone(&self.config.evaluation),
             ));
         }
 
-        fitness_function(&mut organisms);
-        self.organisms = organisms.iter().map(|x| Arc::new(x.clone())).collect();
+        self.organisms = organisms;
+        self.generation = 0;
+        self.completed = false;
+    }
 
-        self.speciate();
+    fn statistics(&self, best_organism: &Organism) -> GenerationStatistics {
+        GenerationStatistics {
+            generation: self.generation,
+            organism_count: self.organisms.len(),
+            species_count: self.species.len(),
+            best_fitness: best_organism.fitness.unwrap(),
+            mean_fitness: self
+                .organisms
+                .iter()
+                .map(|o| o.fitness.unwrap())
+                .sum::<f64>()
+                / self.organisms.len() as f64,
+            best_genome_id: best_organism.genome.id(),
+            best_node_count: best_organism.genome.node_count(),
+            best_connection_count: best_organism.genome.connection_count(),
+            best_enabled_connection_count: best_organism.genome.enabled_connection_count(),
+            pool_node_count: self.pool.nodes.len(),
+            pool_connection_count: self.pool.connections.len(),
+        }
     }
 
     fn speciate(&mut self) {
@@ -154,29 +536,42 @@ impl Population {
 
         // Alte Spezien übernehmen
         for species in &self.species {
-            new_species.push(Species::new(
-                species.select_new_representative(),
+            let mut next = Species::new(
+                species.select_new_representative(&mut self.rng),
                 Arc::clone(&self.config.species),
                 species.id,
-            ));
+                species.created(),
+            );
+            next.inherit_history(species);
+            new_species.push(next);
         }
 
         // Neue Organismen einteilen
-        for organism in &self.organisms {
+        // TODO: Cloning vermeiden
+        for organism in self.organisms.iter().map(|o| Arc::new(o.clone())) {
             let mut found_species = false;
             for species in &mut new_species {
-                if species.matches(Arc::clone(organism), Arc::clone(&self.config.distance)) {
-                    species.add_organism(Arc::clone(organism));
+                if species.matches(
+                    Arc::clone(&organism),
+                    Arc::clone(&self.config.distance),
+                    self.distance_tolerance,
+                ) {
+                    species.add_organism(Arc::clone(&organism));
                     found_species = true;
                     break;
                 }
             }
             if !found_species {
-                println!("Creating a new species");
+                let generation = self.generation;
+                let species_id = self.next_species_id;
+                self.observers
+                    .iter_mut()
+                    .for_each(|observer| observer.on_species_created(generation, species_id));
                 let mut species = Species::new(
                     Arc::clone(&organism),
                     Arc::clone(&self.config.species),
                     self.next_species_id,
+                    self.generation,
                 );
                 self.next_species_id += 1;
                 species.add_organism(Arc::clone(&organism));
@@ -185,71 +580,259 @@ impl Population {
         }
 
         // Ausgelöschte Spezies entfernen
+        for species in new_species.iter().filter(|s| s.organisms.is_empty()) {
+            for observer in &mut self.observers {
+                observer.on_species_extinct(self.generation, species.id);
+            }
+        }
         new_species.retain(|s| !s.organisms.is_empty());
 
         self.species = new_species;
+        self.remove_stagnant_species();
+
+        // Die Toleranz für die nächste Generation anpassen
+        if let Some(dynamic) = &self.config.species.dynamic_tolerance {
+            self.distance_tolerance = dynamic.adjust(self.distance_tolerance, self.species.len());
+        }
     }
 
-    fn write_generation(
-        &self,
-        generation_number: u32,
-        path: &Path,
-        file_type: FileType,
-    ) -> Result<(), String> {
+    // Entfernt Spezies, die sich zu lange nicht verbessert haben. Sie erhalten somit keine Nachkommen mehr.
+    // Die protected_species_count besten Spezies bleiben immer erhalten.
+    fn remove_stagnant_species(&mut self) {
+        let generation = self.generation;
+        self.species
+            .iter_mut()
+            .for_each(|species| species.update_history(generation));
+
+        let mut ranking: Vec<usize> = (0..self.species.len()).collect();
+        ranking.sort_by(|a, b| {
+            compare_fitness(
+                self.species[*b].best_fitness(),
+                self.species[*a].best_fitness(),
+            )
+        });
+        let mut stagnant = Vec::new();
+        for index in ranking
+            .into_iter()
+            .skip(self.config.species.protected_species_count)
+        {
+            let species = &self.species[index];
+            if species.is_stagnant(generation) {
+                let stagnant_generations = species.stagnant_generations(generation);
+                for observer in &mut self.observers {
+                    observer.on_species_stagnated(generation, species.id, stagnant_generations);
+                    observer.on_species_extinct(generation, species.id);
+                }
+                stagnant.push(species.id);
+            }
+        }
+        self.species.retain(|s| !stagnant.contains(&s.id));
+    }
+
+    fn update_phase(&mut self) {
+        if let Some(simplification) = &self.config.reproduction.simplification {
+            let complexity = self
+                .organisms
+                .iter()
+                .map(|o| (o.genome.node_count() + o.genome.connection_count()) as f64)
+                .sum::<f64>()
+                / self.organisms.len() as f64;
+            if self
+                .phase
+                .update(simplification, complexity, self.generation)
+            {
+                let (generation, phase) = (self.generation, self.phase.phase());
+                self.observers
+                    .iter_mut()
+                    .for_each(|observer| observer.on_phase_changed(generation, phase));
+            }
+        }
+    }
+
+    fn write_generation(&self, path: &Path, file_type: FileType) -> Result<(), NeatError> {
+        let generation_number = self.generation;
         let generation = PrintableGeneration {
             generation: generation_number,
             species: self.species.iter().map(|s| s.into()).collect(),
             pool: (&self.pool).into(),
+            organisms: self.organisms.iter().map(|o| o.into()).collect(),
+            next_species_id: self.next_species_id,
+            last_genome_id: self.genome_id_generator.last_id(),
+            distance_tolerance: Some(self.distance_tolerance),
+            phase: (&self.phase).into(),
         };
 
         generation::write(
             generation,
             &path.join("gen-".to_owned() + &generation_number.to_string() + file_type.to_ext()),
             file_type,
-        )
+        )?;
+
+        if self.config.novelty.is_some() {
+            novelty::write(
+                &self.archive,
+                &path.join(
+                    "archive-".to_owned() + &generation_number.to_string() + file_type.to_ext(),
+                ),
+                file_type,
+            )?;
+        }
+        Ok(())
     }
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct PopulationConfig {
     pub target_fitness: f64, // Wird diese Fitness erreicht oder überschritten wird abgebrochen
     pub max_generations: u32, // So viele Generationen werden höchstens durchlaufen (0 entspricht unbegrenzt)
+    pub seed: Option<u64>, // Mit gesetztem Seed sind Läufe mit einer deterministischen Fitnessfunktion reproduzierbar
+    pub evaluation_threads: usize, // Anzahl der Threads, mit denen evolve_parallel bewertet (0 entspricht der Anzahl der CPU-Kerne)
     pub initial_organism_weight: NewConnectionWeight, // So wird das Gewicht der Connections in den initialen Genomen bestimmt
+    pub initial_topology: InitialTopology, // Welche Nodes und Connections die initialen Genome enthalten
     pub distance: Arc<DistanceConfig>,
     pub species: Arc<SpeciesConfig>,
     pub evaluation: Arc<EvaluationConfig>,
     pub reproduction: Arc<ReproductionConfig>,
+    pub novelty: Option<NoveltyConfig>, // Ist novelty gesetzt, wird Novelty Search verwendet
+    pub multi_objective: Option<MultiObjectiveConfig>, // Ist multi_objective gesetzt, wird nach den Pareto-Fronten der Ziele reproduziert (die Fitness bestimmt weiterhin target_fitness und den besten Organismus)
 }
 
-impl PopulationConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        assert_not_negative(self.target_fitness, "target_fitness")
-            .and(self.distance.validate())
-            .and(self.species.validate())
-            .and(self.reproduction.validate())
+impl Default for PopulationConfig {
+    fn default() -> Self {
+        PopulationConfig {
+            target_fitness: f64::MAX,
+            max_generations: 200,
+            seed: None,
+            evaluation_threads: 0,
+            initial_organism_weight: NewConnectionWeight::default(),
+            initial_topology: InitialTopology::default(),
+            distance: Arc::default(),
+            species: Arc::default(),
+            evaluation: Arc::default(),
+            reproduction: Arc::default(),
+            novelty: None,
+            multi_objective: None,
+        }
+    }
+}
+
+impl Validate for PopulationConfig {
+    fn check(&self, validation: &mut Validation) {
+        if self.novelty.is_some() && self.multi_objective.is_some() {
+            validation.error(
+                "multi_objective",
+                "novelty and multi_objective must not be used together",
+            );
+        }
+        validation.not_negative(self.target_fitness, "target_fitness");
+        if self.target_fitness == f64::MAX && self.max_generations == 0 {
+            validation.warning(
+                "max_generations",
+                "is unlimited and target_fitness is not set, evolve only stops through an observer",
+            );
+        }
+        validation.check("initial_organism_weight", &self.initial_organism_weight);
+        validation.check("initial_topology", &self.initial_topology);
+        validation.check("distance", self.distance.as_ref());
+        validation.check("species", self.species.as_ref());
+        validation.check("evaluation", self.evaluation.as_ref());
+        validation.check("reproduction", self.reproduction.as_ref());
+        validation.check("novelty", &self.novelty);
+        validation.check("multi_objective", &self.multi_objective);
+
+        // Jede der erwarteten Spezies muss min_species_size Organismen erhalten können
+        let species_count = self
+            .species
+            .dynamic_tolerance
+            .as_ref()
+            .map_or(1, |dynamic| dynamic.target_species_count);
+        if self.reproduction.min_species_size * species_count > self.reproduction.organism_count {
+            validation.error(
+                "reproduction.min_species_size",
+                &format!(
+                    "{} organisms for each of the {} expected species exceed organism_count",
+                    self.reproduction.min_species_size, species_count
+                ),
+            );
+        }
+    }
+}
+
+// Jede Generation erhält einen eigenen Zufallsgenerator, der nur vom Seed und der Nummer der Generation
+// abhängt. Dadurch verläuft auch eine mit resume fortgesetzte Evolution identisch. Der Seed wird vor dem
+// Verknüpfen mit der Generation gemischt, damit verschiedene Paare aus Seed und Generation (z.B. Generation
+// g + 1 von Seed s und Generation g von Seed s + 1) nicht den gleichen Zufall erhalten.
+fn generation_rng(seed: Option<u64>, generation: u32) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(splitmix64(splitmix64(seed) ^ generation as u64)),
+        None => StdRng::from_entropy(),
+    }
+}
+
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn all_finite(values: Option<&Vec<f64>>) -> bool {
+    values.is_some_and(|values| values.iter().all(|value| value.is_finite()))
+}
+
+// Überträgt die Einstellungen, die der GenePool beim Erzeugen von Genen benötigt
+fn configure_pool(pool: &mut GenePool, config: &PopulationConfig) {
+    pool.allow_recurrent = config.evaluation.network_type.allows_recurrence();
+    pool.default_activation = config.evaluation.activation;
+    pool.default_bias = config.evaluation.bias;
+}
+
+// Sucht die Datei gen-N.* mit dem größten N
+fn find_latest_generation(target_path: &Path) -> Result<PathBuf, NeatError> {
+    let mut latest: Option<(u32, PathBuf)> = None;
+    let io_error = |err| NeatError::io(target_path.to_owned(), err);
+    for entry in fs::read_dir(target_path).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let number = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_prefix("gen-"))
+            .and_then(|number| number.parse::<u32>().ok());
+        if let Some(number) = number {
+            if path.is_file() && latest.as_ref().is_none_or(|(n, _)| number > *n) {
+                latest = Some((number, path));
+            }
+        }
     }
+    latest.map(|(_, path)| path).ok_or_else(|| {
+        NeatError::InvalidState("target_path contains no generation to resume from".to_owned())
+    })
 }
 
-fn prepare_target_directory(target_path: &Path) -> Result<(), String> {
+fn prepare_target_directory(target_path: &Path) -> Result<(), NeatError> {
     if target_path.is_file() {
-        return Err("target_path refers to a file".to_owned());
+        return Err(NeatError::InvalidState(
+            "target_path refers to a file".to_owned(),
+        ));
     }
 
+    let io_error = |err| NeatError::io(target_path.to_owned(), err);
     if target_path.exists() {
-        fs::remove_dir_all(target_path).map_err(|err| err.to_string())?;
+        fs::remove_dir_all(target_path).map_err(io_error)?;
     }
-    fs::create_dir_all(target_path).map_err(|err| err.to_string())
+    fs::create_dir_all(target_path).map_err(io_error)
 }
 
 fn write_result(
-    best_organism: Arc<Organism>,
+    best_organism: &Organism,
     final_pool: &GenePool,
     path: &Path,
     file_type: FileType,
-) {
+) -> Result<(), NeatError> {
     let result = PrintableNeatResult {
         best_genome: (&best_organism.genome).into(),
-        best_fitness: best_organism.fitness.unwrap(),
+        best_fitness: best_organism.fitness.ok_or(NeatError::MissingFitness)?,
         final_pool: final_pool.into(),
     };
 
@@ -258,5 +841,4 @@ fn write_result(
         &path.join("result".to_owned() + file_type.to_ext()),
         file_type,
     )
-    .unwrap();
 }
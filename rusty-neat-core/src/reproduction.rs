@@ -5,99 +5,205 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
-use rand::prelude::SliceRandom;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
+
+use rusty_neat_interchange::generation::PrintablePhase;
 
 use crate::{
-    config_util::assert_not_negative,
+    config::{Validate, Validation},
     gene_pool::GenePool,
-    genome::{CrossoverConfig, EvaluationConfig, GenomeIdGenerator, MutationConfig},
+    genome::{
+        CrossoverConfig, CrossoverOperator, DistanceConfig, EvaluationConfig, Genome,
+        GenomeIdGenerator, MutationConfig,
+    },
     organism::Organism,
     population::Population,
     species::Species,
 };
 
-pub(crate) fn reproduce(
+pub(crate) fn reproduce<R: Rng>(
     population: &mut Population,
     config: Arc<ReproductionConfig>,
     evaluation_config: Arc<EvaluationConfig>,
+    distance_config: Arc<DistanceConfig>,
     generation: u32,
+    rng: &mut R,
 ) -> Vec<Organism> {
     let mut new_population: Vec<Organism> = Vec::with_capacity(config.organism_count);
 
-    let mut total_fitness: f64 = population
+    // In einer Vereinfachungsphase mutieren alle Organismen mit der Konfiguration der Vereinfachung
+    let simplification = match (&config.simplification, population.phase.phase()) {
+        (Some(simplification), Phase::Simplifying) => Some(&simplification.mutation),
+        _ => None,
+    };
+
+    let fitness: Vec<f64> = population
         .species
         .iter_mut()
         .map(|s| s.adjusted_fitness())
-        .sum();
-    if total_fitness == 0.0 {
-        total_fitness = 1.0;
-    }
+        .collect();
+    let target_counts =
+        allocate_offspring(&fitness, config.organism_count, config.min_species_size);
 
+    // Organismen innerhalb der Spezies sortieren und die möglichen Eltern jeder Spezies bestimmen (bei der
+    // Kreuzung zwischen Spezies stammt der zweite Elternteil aus einer anderen Spezies)
     for species in &mut population.species {
-        let target_count = (species.adjusted_fitness() / total_fitness
-            * config.organism_count as f64)
-            .round() as usize;
+        species.organisms.sort_unstable();
+    }
+    let parents: Vec<Parents> = population
+        .species
+        .iter()
+        .map(|species| Parents::new(species, config.kill_ratio))
+        .collect();
+
+    for (index, target_count) in target_counts.into_iter().enumerate() {
         reproduce_species(
-            species,
+            &parents,
+            index,
             target_count,
             &mut population.pool,
             config.as_ref(),
+            distance_config.as_ref(),
             Arc::clone(&evaluation_config),
             &mut new_population,
             &mut population.genome_id_generator,
             generation,
+            simplification,
+            rng,
         )
     }
 
     new_population
 }
 
-fn reproduce_species(
-    species: &mut Species,
+// Verteilt genau total Nachkommen auf die Spezies, proportional zu ihrem Anteil shares (negative Anteile zählen
+// als 0, sind alle 0, erhalten alle Spezies den gleichen Anteil). Für die Mindestgröße gilt:
+// - Spezies, deren Anteil weniger als min_size Nachkommen ergäbe, erhalten genau min_size. Der Rest wird
+//   proportional auf die übrigen Spezies verteilt (wiederholt, bis alle übrigen mindestens min_size erhalten).
+// - Reicht total nicht für min_size Nachkommen je Spezies, sinkt die Mindestgröße auf total / Anzahl der Spezies.
+// Gebrochene Anteile werden nach dem Largest-Remainder-Verfahren gerundet: Jede Spezies erhält zunächst den
+// abgerundeten Anteil, die übrigen Nachkommen gehen an die Spezies mit den größten Nachkommastellen (bei
+// Gleichstand an die vordere).
+pub fn allocate_offspring(shares: &[f64], total: usize, min_size: usize) -> Vec<usize> {
+    if shares.is_empty() {
+        return Vec::new();
+    }
+    let min_size = min(min_size, total / shares.len());
+    let shares: Vec<f64> = if shares.iter().any(|share| *share > 0.0) {
+        shares
+            .iter()
+            .map(|share| if *share > 0.0 { *share } else { 0.0 })
+            .collect()
+    } else {
+        vec![1.0; shares.len()]
+    };
+
+    let mut counts = vec![min_size; shares.len()];
+    let mut free: Vec<usize> = (0..shares.len()).collect(); // Spezies, die mehr als min_size erhalten können
+    loop {
+        let rest = total - min_size * (shares.len() - free.len());
+        let free_shares: f64 = free.iter().map(|&i| shares[i]).sum();
+        let quota = |i: usize| shares[i] / free_shares * rest as f64;
+        let (fixed, remaining): (Vec<usize>, Vec<usize>) = free
+            .iter()
+            .partition(|&&i| free_shares == 0.0 || quota(i) < min_size as f64);
+        if remaining.is_empty() {
+            // Die übrigen Spezies haben keinen Anteil, der Rest wird gleichmäßig verteilt
+            let equal = rest as f64 / free.len() as f64;
+            largest_remainder(&mut counts, free.iter().map(|&i| (i, equal)), rest);
+            return counts;
+        }
+        if fixed.is_empty() {
+            largest_remainder(&mut counts, free.iter().map(|&i| (i, quota(i))), rest);
+            return counts;
+        }
+        free = remaining;
+    }
+}
+
+// Setzt counts[i] auf die abgerundeten Quoten und verteilt die bis total fehlenden Nachkommen nach den größten
+// Nachkommastellen
+fn largest_remainder<I: Iterator<Item = (usize, f64)>>(
+    counts: &mut [usize],
+    quotas: I,
+    total: usize,
+) {
+    let mut remainders = Vec::new();
+    let mut assigned = 0;
+    for (i, quota) in quotas {
+        counts[i] = quota.floor() as usize;
+        assigned += counts[i];
+        remainders.push((i, quota - quota.floor()));
+    }
+    remainders.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    for (i, _) in remainders.into_iter().take(total.saturating_sub(assigned)) {
+        counts[i] += 1;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn reproduce_species<R: Rng>(
+    parents: &[Parents],
+    index: usize,
     target_count: usize,
     pool: &mut GenePool,
     config: &ReproductionConfig,
+    distance_config: &DistanceConfig,
     evaluation_config: Arc<EvaluationConfig>,
     new_population: &mut Vec<Organism>,
     genome_id_generator: &mut GenomeIdGenerator,
     generation: u32,
+    simplification: Option<&MutationConfig>,
+    rng: &mut R,
 ) {
-    // Organismen innerhalb der Spezies sortieren
-    species.organisms.sort_unstable();
+    let own = &parents[index];
+    let species = own.species;
 
-    // Die schlechtesten Limit organismen werden für die Kreuzung nicht betrachtet
-    let limit = max(
-        (species.organisms.len() as f64 * config.kill_ratio) as usize,
-        species.organisms.len() - 1,
-    );
+    // Die Mutation richtet sich nach der Stufe der Spezies, in einer Vereinfachungsphase gilt deren Konfiguration
+    let mutation_config = match simplification {
+        Some(simplification) => simplification,
+        None => config.mutation_tier(species, generation - 1),
+    };
+    let annealed;
+    let mutation_config = match &config.annealing {
+        Some(annealing) => {
+            annealed = mutation_config.annealed(annealing.factor(generation));
+            &annealed
+        }
+        None => mutation_config,
+    };
 
-    // In der Spezies müssen nach der Reduktion mindestens min_species_size viele Organismen sein
-    let target_count = max(target_count, config.min_species_size);
-
-    // Elitismus
-    let elitism_count = min(config.elitism_count, target_count);
-    if config.allow_elitism && species.organisms.len() >= config.elitism_limit {
-        new_population.extend(
-            species
-                .organisms
-                .iter()
-                .skip(species.organisms.len() - config.elitism_count)
-                .map(|x| (**x).clone()),
-        );
-    }
+    // Elitismus (höchstens target_count Organismen, damit die Spezies genau target_count Nachkommen hat)
+    let elitism_count = if config.allow_elitism && species.organisms.len() >= config.elitism_limit {
+        min(
+            min(config.elitism_count, target_count),
+            species.organisms.len(),
+        )
+    } else {
+        0
+    };
+    new_population.extend(
+        species
+            .organisms
+            .iter()
+            .skip(species.organisms.len() - elitism_count)
+            .map(|x| (**x).clone()),
+    );
 
     // Reine Mutationen
     let mutation_count = (config.mutation_ratio * (target_count - elitism_count) as f64) as usize;
     for _ in 0..mutation_count {
-        let parent = select_parent(species, &config.species_strategy, limit);
+        let parent = own.select(&config.species_strategy, rng);
         let mut offspring = (*parent).clone();
-        mutate_organism(
-            &mut offspring,
+        offspring.genome_mut().mutate(
             pool,
-            target_count,
-            config,
+            mutation_config,
             genome_id_generator.next_id(),
             generation,
+            rng,
         );
         offspring.fitness = None;
         new_population.push(offspring);
@@ -105,67 +211,89 @@ fn reproduce_species(
 
     // Kreuzung
     for _ in 0..(target_count - elitism_count - mutation_count) {
-        let first_parent = select_parent(species, &config.species_strategy, limit);
-        let second_parent = select_parent(species, &config.species_strategy, limit);
-
-        let mut offspring = Organism::new(
-            first_parent.genome.crossover(
-                &second_parent.genome,
-                pool,
-                &config.crossover,
-                genome_id_generator.next_id(),
-                generation,
-            ),
-            Arc::clone(&evaluation_config),
-        );
+        let selected: Vec<Arc<Organism>> = match config.crossover.operator {
+            // Alle Eltern stammen aus der eigenen Spezies
+            CrossoverOperator::MultiParent { parents: count } => (0..count)
+                .map(|_| own.select(&config.species_strategy, rng))
+                .collect(),
+            _ => {
+                let first_parent = own.select(&config.species_strategy, rng);
+                let second_parent = match &config.interspecies_mating {
+                    Some(mating) if parents.len() > 1 && rng.gen_bool(mating.rate) => {
+                        let other = mating.partner.choose(
+                            parents,
+                            index,
+                            &first_parent,
+                            distance_config,
+                            rng,
+                        );
+                        parents[other].select(&config.species_strategy, rng)
+                    }
+                    _ => own.select(&config.species_strategy, rng),
+                };
+                vec![first_parent, second_parent]
+            }
+        };
+        let genomes: Vec<(&Genome, f64)> = selected
+            .iter()
+            .map(|parent| (&parent.genome, parent.selection_fitness().unwrap()))
+            .collect();
 
-        mutate_organism(
-            &mut offspring,
+        // Es werden immer mindestens zwei Eltern ausgewählt
+        let genome = Genome::crossover(
+            &genomes,
             pool,
-            target_count,
-            config,
+            &config.crossover,
+            genome_id_generator.next_id(),
+            generation,
+            rng,
+        )
+        .unwrap();
+        let mut offspring = Organism::new(genome, Arc::clone(&evaluation_config));
+
+        offspring.genome_mut().mutate(
+            pool,
+            mutation_config,
             genome_id_generator.next_id(),
             generation,
+            rng,
         );
         new_population.push(offspring);
     }
 }
 
-fn select_parent(
-    species: &Species,
-    strategy: &SpeciesReproductionStrategy,
-    limit: usize,
-) -> Arc<Organism> {
-    match strategy {
-        SpeciesReproductionStrategy::Random => Arc::clone(
-            species.organisms[limit..]
-                .choose(&mut rand::thread_rng())
-                .unwrap(),
-        ),
-        //SpeciesReproductionStrategy::AdjustedRandom => Rc::clone(species.organisms.iter().choose_weighted(&mut rand::thread_rng(), |o| o.fitness.unwrap()).unwrap())
-    }
+// Die Organismen einer (sortierten) Spezies, die als Eltern ausgewählt werden können
+struct Parents<'a> {
+    species: &'a Species,
+    limit: usize, // Die schlechtesten limit Organismen werden für die Reproduktion nicht betrachtet
+    fitness: Vec<f64>, // Fitness der Organismen ab limit
 }
 
-fn mutate_organism(
-    organism: &mut Organism,
-    pool: &mut GenePool,
-    species_size: usize,
-    config: &ReproductionConfig,
-    new_id: u64,
-    generation: u32,
-) {
-    if species_size >= config.large_species_size {
-        organism
-            .genome
-            .mutate(pool, &config.large_intensity_config, new_id, generation);
-    } else {
-        organism
-            .genome
-            .mutate(pool, &config.large_intensity_config, new_id, generation);
+impl<'a> Parents<'a> {
+    fn new(species: &'a Species, kill_ratio: f64) -> Self {
+        // Mindestens ein Organismus bleibt übrig
+        let limit = min(
+            (species.organisms.len() as f64 * kill_ratio) as usize,
+            species.organisms.len() - 1,
+        );
+        let fitness = species.organisms[limit..]
+            .iter()
+            .map(|o| o.selection_fitness().unwrap())
+            .collect();
+        Parents {
+            species,
+            limit,
+            fitness,
+        }
+    }
+
+    fn select<R: Rng>(&self, strategy: &SpeciesReproductionStrategy, rng: &mut R) -> Arc<Organism> {
+        Arc::clone(&self.species.organisms[self.limit + strategy.select(&self.fitness, rng)])
     }
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ReproductionConfig {
     pub organism_count: usize,   // Anzahl der Organismen in jeder Generation
     pub min_species_size: usize, // Minimale Anzahl an Organismen in einer Spezies
@@ -175,27 +303,387 @@ pub struct ReproductionConfig {
     pub elitism_limit: usize, // Minimale Anzahl an Genomen in einer Spezies, damit elitism_count Genome unverändert in die nächste Generation übernommen werden
     pub elitism_count: usize, // Anzahl der Organismen einer Spezies die unverändert in die nächste Generation übernommen werden. Muss <= elitism_limit sein
     pub species_strategy: SpeciesReproductionStrategy, // Wie die Organismen sich innerhalb einer Spezies reproduzieren
-    pub large_species_size: usize, // Ab dieser Anzahl an Organismen zählt eine Spezies als groß und mutiert stärker
-    pub crossover: CrossoverConfig, // Wie die Kreuzung funktionieren soll
-    pub small_intensity_config: MutationConfig, // Wie eine Mutation mit geringer Intensität erfolgen soll
-    pub large_intensity_config: MutationConfig, // Wie eine Mutation mit hoher Intensität erfolgen soll
+    pub mutation_tiers: Vec<MutationTier>, // Die erste Stufe, deren Bedingung eine Spezies erfüllt, bestimmt die Mutationen ihrer Nachkommen
+    pub crossover: CrossoverConfig,        // Wie die Kreuzung funktionieren soll
+    pub annealing: Option<AnnealingConfig>, // Ist annealing gesetzt, werden die Verschiebungen von Gewichten und Bias mit den Generationen kleiner
+    pub simplification: Option<SimplificationConfig>, // Ist simplification gesetzt, wechseln sich Phasen der Komplexifizierung und Vereinfachung ab (Phased Pruning)
+    pub interspecies_mating: Option<InterspeciesMatingConfig>, // Ist interspecies_mating gesetzt, stammt der zweite Elternteil einer Kreuzung manchmal aus einer anderen Spezies
+}
+
+impl Default for ReproductionConfig {
+    fn default() -> Self {
+        ReproductionConfig {
+            organism_count: 1000,
+            min_species_size: 10,
+            kill_ratio: 0.5,
+            mutation_ratio: 0.5,
+            allow_elitism: true,
+            elitism_limit: 20,
+            elitism_count: 2,
+            species_strategy: SpeciesReproductionStrategy::Random,
+            mutation_tiers: vec![MutationTier::default()],
+            crossover: CrossoverConfig::default(),
+            annealing: None,
+            simplification: None,
+            interspecies_mating: None,
+        }
+    }
+}
+
+impl Validate for ReproductionConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.not_zero(self.organism_count, "organism_count");
+        validation.ratio(self.kill_ratio, "kill_ratio");
+        validation.ratio(self.mutation_ratio, "mutation_ratio");
+        if self.elitism_count > self.elitism_limit {
+            validation.error("elitism_count", "must not be greater than elitism_limit");
+        }
+        validation.check("species_strategy", &self.species_strategy);
+        check_tiers(&self.mutation_tiers, validation);
+        validation.check("crossover", &self.crossover);
+        validation.check("annealing", &self.annealing);
+        validation.check("simplification", &self.simplification);
+        validation.check("interspecies_mating", &self.interspecies_mating);
+    }
 }
 
 impl ReproductionConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        if self.organism_count == 0 {
-            return Err(String::from("organism_count must not be 0"));
+    // Die Mutationskonfiguration der ersten Stufe, deren Bedingung die Spezies in der Generation erfüllt
+    pub fn mutation_tier(&self, species: &Species, generation: u32) -> &MutationConfig {
+        self.mutation_tiers
+            .iter()
+            .find(|tier| tier.condition.matches(species, generation))
+            .map(|tier| &tier.config)
+            .unwrap_or(&self.mutation_tiers.last().unwrap().config)
+    }
+}
+
+fn check_tiers(tiers: &[MutationTier], validation: &mut Validation) {
+    if !matches!(
+        tiers.last(),
+        Some(MutationTier {
+            condition: TierCondition::Always,
+            ..
+        })
+    ) {
+        validation.error(
+            "mutation_tiers",
+            "the last mutation tier must have the condition Always",
+        );
+    }
+    for (i, tier) in tiers.iter().enumerate() {
+        validation.check(&format!("mutation_tiers[{}].config", i), &tier.config);
+    }
+    // Hinter einer Stufe mit Always wird keine weitere Stufe mehr erreicht
+    if let Some(always) = tiers
+        .iter()
+        .position(|tier| matches!(tier.condition, TierCondition::Always))
+    {
+        for i in always + 1..tiers.len() {
+            validation.warning(
+                &format!("mutation_tiers[{}]", i),
+                "is unreachable because an earlier tier has the condition Always",
+            );
         }
-        assert_not_negative(self.kill_ratio, "kill_ratio")
-            .and(assert_not_negative(self.mutation_ratio, "mutation_ratio"))
-            .and(self.small_intensity_config.validate())
-            .and(self.crossover.validate())
-            .and(self.large_intensity_config.validate())
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct MutationTier {
+    pub condition: TierCondition,
+    pub config: MutationConfig,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub enum TierCondition {
+    #[default]
+    Always,
+    MinSpeciesSize(usize), // Spezies mit mindestens so vielen Organismen
+    MinSpeciesAge(u32),    // Spezies, die seit mindestens so vielen Generationen existieren
+    MinStagnation(u32), // Spezies, die sich seit mindestens so vielen Generationen nicht verbessert haben
+}
+
+impl TierCondition {
+    pub fn matches(&self, species: &Species, generation: u32) -> bool {
+        match self {
+            TierCondition::Always => true,
+            TierCondition::MinSpeciesSize(size) => species.organisms().len() >= *size,
+            TierCondition::MinSpeciesAge(age) => species.age(generation) >= *age,
+            TierCondition::MinStagnation(generations) => {
+                species.stagnant_generations(generation) >= *generations
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnnealingConfig {
+    pub decay: f64, // Faktor, mit dem die Standardabweichung der Verschiebungen pro Generation multipliziert wird
+    pub min_factor: f64, // Untere Grenze des gesamten Faktors
+}
+
+impl Default for AnnealingConfig {
+    fn default() -> Self {
+        AnnealingConfig {
+            decay: 0.99,
+            min_factor: 0.1,
+        }
+    }
+}
+
+impl Validate for AnnealingConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.ratio(self.decay, "decay");
+        validation.ratio(self.min_factor, "min_factor");
+    }
+}
+
+impl AnnealingConfig {
+    // Der Faktor für die Standardabweichungen in der gegebenen Generation
+    pub fn factor(&self, generation: u32) -> f64 {
+        self.decay.powf(generation as f64).max(self.min_factor)
     }
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimplificationConfig {
+    pub complexity_threshold: f64, // Eine Vereinfachungsphase beginnt, wenn die mittlere Komplexität (Nodes und Connections pro Genome) die Komplexität nach der letzten Vereinfachung um diesen Wert übersteigt
+    pub stagnation_limit: u32, // Die Vereinfachungsphase endet, wenn die mittlere Komplexität so viele Generationen nicht gesunken ist
+    pub mutation: MutationConfig, // Mutationen während der Vereinfachung (typischerweise mit delete_*_prob statt add_*_prob)
+}
+
+impl Default for SimplificationConfig {
+    fn default() -> Self {
+        SimplificationConfig {
+            complexity_threshold: 10.0,
+            stagnation_limit: 10,
+            mutation: MutationConfig {
+                add_node_prob: 0.0,
+                add_connection_prob: 0.0,
+                delete_node_prob: 0.03,
+                delete_connection_prob: 0.05,
+                ..MutationConfig::default()
+            },
+        }
+    }
+}
+
+impl Validate for SimplificationConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.not_zero(self.stagnation_limit as usize, "stagnation_limit");
+        validation.not_negative(self.complexity_threshold, "complexity_threshold");
+        validation.check("mutation", &self.mutation);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InterspeciesMatingConfig {
+    pub rate: f64, // Wahrscheinlichkeit, dass der zweite Elternteil einer Kreuzung aus einer anderen Spezies stammt
+    pub partner: InterspeciesPartner, // Aus welcher Spezies der zweite Elternteil stammt
+}
+
+impl Default for InterspeciesMatingConfig {
+    fn default() -> Self {
+        InterspeciesMatingConfig {
+            rate: 0.001,
+            partner: InterspeciesPartner::Random,
+        }
+    }
+}
+
+impl Validate for InterspeciesMatingConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.probability(self.rate, "rate");
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub enum InterspeciesPartner {
+    #[default]
+    Random, // Eine zufällige andere Spezies
+    Nearest, // Die Spezies, deren Representative dem ersten Elternteil am ähnlichsten ist (siehe Genome::distance)
+}
+
+impl InterspeciesPartner {
+    // Der Index der Spezies des zweiten Elternteils. parents enthält mindestens zwei Spezies.
+    fn choose<R: Rng>(
+        &self,
+        parents: &[Parents],
+        index: usize,
+        first_parent: &Organism,
+        distance_config: &DistanceConfig,
+        rng: &mut R,
+    ) -> usize {
+        match self {
+            InterspeciesPartner::Random => {
+                let other = rng.gen_range(0..parents.len() - 1);
+                if other >= index {
+                    other + 1
+                } else {
+                    other
+                }
+            }
+            InterspeciesPartner::Nearest => {
+                (0..parents.len())
+                    .filter(|&other| other != index)
+                    .map(|other| {
+                        let representative = parents[other].species.representative();
+                        let distance = first_parent
+                            .genome
+                            .distance(&representative.genome, distance_config);
+                        (other, distance)
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap()
+                    .0
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Complexifying,
+    Simplifying,
+}
+
+// Zustand des Phased Pruning
+#[derive(Clone, Debug)]
+pub(crate) struct PhaseState {
+    phase: Phase,
+    complexity_floor: Option<f64>, // Mittlere Komplexität am Ende der letzten Vereinfachung (bzw. der ersten Generation)
+    lowest_complexity: f64,        // Niedrigste mittlere Komplexität der aktuellen Vereinfachung
+    last_decrease: u32, // Generation, in der die mittlere Komplexität zuletzt gesunken ist
+}
+
+impl PhaseState {
+    pub(crate) fn new() -> Self {
+        PhaseState {
+            phase: Phase::Complexifying,
+            complexity_floor: None,
+            lowest_complexity: 0.0, // Wird zu Beginn einer Vereinfachung gesetzt
+            last_decrease: 0,
+        }
+    }
+
+    pub(crate) fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    // Aktualisiert die Phase mit der mittleren Komplexität der abgeschlossenen Generation. Gibt true zurück,
+    // falls die Phase gewechselt hat.
+    pub(crate) fn update(
+        &mut self,
+        config: &SimplificationConfig,
+        complexity: f64,
+        generation: u32,
+    ) -> bool {
+        match self.phase {
+            Phase::Complexifying => {
+                let floor = *self.complexity_floor.get_or_insert(complexity);
+                if complexity > floor + config.complexity_threshold {
+                    self.phase = Phase::Simplifying;
+                    self.lowest_complexity = complexity;
+                    self.last_decrease = generation;
+                    return true;
+                }
+            }
+            Phase::Simplifying => {
+                if complexity < self.lowest_complexity {
+                    self.lowest_complexity = complexity;
+                    self.last_decrease = generation;
+                } else if generation - self.last_decrease >= config.stagnation_limit {
+                    self.phase = Phase::Complexifying;
+                    self.complexity_floor = Some(complexity);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl From<&PhaseState> for PrintablePhase {
+    fn from(state: &PhaseState) -> Self {
+        PrintablePhase {
+            simplifying: state.phase == Phase::Simplifying,
+            complexity_floor: state.complexity_floor,
+            lowest_complexity: state.lowest_complexity,
+            last_decrease: state.last_decrease,
+        }
+    }
+}
+
+impl From<&PrintablePhase> for PhaseState {
+    fn from(printable: &PrintablePhase) -> Self {
+        PhaseState {
+            phase: if printable.simplifying {
+                Phase::Simplifying
+            } else {
+                Phase::Complexifying
+            },
+            complexity_floor: printable.complexity_floor,
+            lowest_complexity: printable.lowest_complexity,
+            last_decrease: printable.last_decrease,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
 pub enum SpeciesReproductionStrategy {
+    #[default]
     Random, // Es werden zufällige Eltern ausgewählt
-            // TODO AdjustedRandom    // Bessere Organismen haben eine höhere Wahrscheinlichkeit, Eltern zu werden
+    Roulette, // Die Wahrscheinlichkeit ist proportional zur Fitness (negative Fitness wird verschoben)
+    Rank, // Die Wahrscheinlichkeit ist proportional zum Rang innerhalb der Spezies (der schlechteste hat Rang 1)
+    Tournament {
+        size: usize,
+    }, // Der beste von size zufällig (mit Zurücklegen) ausgewählten Organismen
+    Truncation {
+        ratio: f64,
+    }, // Ein zufälliger Organismus aus dem besten Anteil ratio
+}
+
+impl Validate for SpeciesReproductionStrategy {
+    fn check(&self, validation: &mut Validation) {
+        match *self {
+            SpeciesReproductionStrategy::Tournament { size } => validation.not_zero(size, "size"),
+            SpeciesReproductionStrategy::Truncation { ratio } if ratio <= 0.0 => {
+                validation.error("ratio", "must be greater than 0")
+            }
+            SpeciesReproductionStrategy::Truncation { ratio } => validation.ratio(ratio, "ratio"),
+            _ => {}
+        }
+    }
+}
+
+impl SpeciesReproductionStrategy {
+    // Wählt den Index eines Elternteils aus. fitness muss aufsteigend sortiert und darf nicht leer sein.
+    pub fn select<R: Rng>(&self, fitness: &[f64], rng: &mut R) -> usize {
+        match self {
+            SpeciesReproductionStrategy::Random => rng.gen_range(0..fitness.len()),
+            SpeciesReproductionStrategy::Roulette => {
+                let offset = fitness[0].min(0.0);
+                let weights = fitness.iter().map(|f| f - offset);
+                match WeightedIndex::new(weights) {
+                    Ok(dist) => dist.sample(rng),
+                    Err(_) => rng.gen_range(0..fitness.len()), // Alle Organismen haben die Fitness 0
+                }
+            }
+            SpeciesReproductionStrategy::Rank => {
+                WeightedIndex::new(1..=fitness.len()).unwrap().sample(rng)
+            }
+            SpeciesReproductionStrategy::Tournament { size } => (0..*size)
+                .map(|_| rng.gen_range(0..fitness.len()))
+                .max()
+                .unwrap(),
+            SpeciesReproductionStrategy::Truncation { ratio } => {
+                let count = max((fitness.len() as f64 * ratio).round() as usize, 1);
+                rng.gen_range(fitness.len() - count..fitness.len())
+            }
+        }
+    }
 }
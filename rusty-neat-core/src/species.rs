@@ -1,35 +1,49 @@
-use std::{
-    sync::{Arc},
-};
+use std::{cmp::Ordering, sync::Arc};
 
-use rand::prelude::SliceRandom;
+use rand::{prelude::SliceRandom, Rng};
 use rusty_neat_interchange::species::PrintableSpecies;
 
-use crate::{config_util, gene_pool::GenePool, genome::{DistanceConfig, EvaluationConfig}, organism::Organism};
+use crate::{
+    config::{Validate, Validation},
+    gene_pool::GenePool,
+    genome::{DistanceConfig, EvaluationConfig},
+    organism::Organism,
+};
 
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone)]
-pub(crate) struct Species {
+pub struct Species {
     pub(crate) organisms: Vec<Arc<Organism>>,
     pub(crate) id: usize,
     representative: Arc<Organism>,
     fitness: Option<f64>,
     config: Arc<SpeciesConfig>,
+    best_fitness: Option<f64>, // Beste jemals in der Spezies erreichte Fitness
+    last_improvement: u32,     // Generation, in der best_fitness zuletzt verbessert wurde
+    created: u32,              // Generation, in der die Spezies entstanden ist
 }
 
 impl Species {
-    pub fn new(representative: Arc<Organism>, config: Arc<SpeciesConfig>, id: usize) -> Species {
+    pub(crate) fn new(
+        representative: Arc<Organism>,
+        config: Arc<SpeciesConfig>,
+        id: usize,
+        created: u32,
+    ) -> Species {
         Species {
             organisms: Vec::new(),
             representative,
             fitness: Option::None,
             config,
             id,
+            best_fitness: None,
+            last_improvement: created,
+            created,
         }
     }
 
-    pub fn from_printable(
+    pub(crate) fn from_printable(
         printable: &PrintableSpecies,
         pool: &GenePool,
         config: Arc<SpeciesConfig>,
@@ -42,10 +56,13 @@ impl Species {
                 Arc::clone(&evaluation_config),
             )),
             Arc::clone(&config),
-            printable.id
+            printable.id,
+            printable.created,
         );
 
         species.fitness = printable.fitness;
+        species.best_fitness = printable.best_fitness;
+        species.last_improvement = printable.last_improvement;
 
         for organism in &printable.organisms {
             species.organisms.push(Arc::from(Organism::from_printable(
@@ -58,7 +75,70 @@ impl Species {
         species
     }
 
-    pub fn adjusted_fitness(&mut self) -> f64 {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn organisms(&self) -> &[Arc<Organism>] {
+        &self.organisms
+    }
+
+    pub fn representative(&self) -> &Organism {
+        &self.representative
+    }
+
+    pub fn best_fitness(&self) -> Option<f64> {
+        self.best_fitness
+    }
+
+    pub fn last_improvement(&self) -> u32 {
+        self.last_improvement
+    }
+
+    // Übernimmt den Verlauf einer Spezies aus der vorherigen Generation
+    pub(crate) fn inherit_history(&mut self, previous: &Species) {
+        self.best_fitness = previous.best_fitness;
+        self.last_improvement = previous.last_improvement;
+    }
+
+    // Aktualisiert best_fitness mit den Organismen der aktuellen Generation
+    pub(crate) fn update_history(&mut self, generation: u32) {
+        let best = self
+            .organisms
+            .iter()
+            .map(|o| o.selection_fitness().unwrap())
+            .max_by(|x, y| x.total_cmp(y));
+        if let Some(best) = best {
+            if self
+                .best_fitness
+                .is_none_or(|best_fitness| best > best_fitness)
+            {
+                self.best_fitness = Some(best);
+                self.last_improvement = generation;
+            }
+        }
+    }
+
+    pub fn created(&self) -> u32 {
+        self.created
+    }
+
+    // Anzahl der Generationen seit dem Entstehen der Spezies
+    pub fn age(&self, generation: u32) -> u32 {
+        generation.saturating_sub(self.created)
+    }
+
+    // Anzahl der Generationen seit der letzten Verbesserung
+    pub fn stagnant_generations(&self, generation: u32) -> u32 {
+        generation.saturating_sub(self.last_improvement)
+    }
+
+    pub(crate) fn is_stagnant(&self, generation: u32) -> bool {
+        self.config.stagnation_limit != 0
+            && self.stagnant_generations(generation) >= self.config.stagnation_limit
+    }
+
+    pub(crate) fn adjusted_fitness(&mut self) -> f64 {
         if self.fitness.is_none() {
             self.fitness = self.try_adjusted_fitness();
         }
@@ -66,21 +146,24 @@ impl Species {
         self.fitness.unwrap()
     }
 
-    pub fn add_organism(&mut self, organism: Arc<Organism>) {
+    pub(crate) fn add_organism(&mut self, organism: Arc<Organism>) {
         self.organisms.push(organism);
         self.fitness = None;
     }
 
-    pub fn matches(&self, organism: Arc<Organism>, config: Arc<DistanceConfig>) -> bool {
-        self.representative.distance(&organism, config) <= self.config.species_distance_tolerance
+    pub(crate) fn matches(
+        &self,
+        organism: Arc<Organism>,
+        config: Arc<DistanceConfig>,
+        tolerance: f64,
+    ) -> bool {
+        self.representative.distance(&organism, config) <= tolerance
     }
 
-    pub fn select_new_representative(&self) -> Arc<Organism> {
+    pub(crate) fn select_new_representative<R: Rng>(&self, rng: &mut R) -> Arc<Organism> {
         match self.config.representative {
-            ReprentativeSelection::First => Arc::clone(self.organisms.iter().next().unwrap()),
-            ReprentativeSelection::Random => {
-                Arc::clone(self.organisms.choose(&mut rand::thread_rng()).unwrap())
-            }
+            ReprentativeSelection::First => Arc::clone(self.organisms.first().unwrap()),
+            ReprentativeSelection::Random => Arc::clone(self.organisms.choose(rng).unwrap()),
         }
     }
 
@@ -93,13 +176,13 @@ impl Species {
                     FitnessStrategy::Best => self
                         .organisms
                         .iter()
-                        .map(|o| o.fitness.unwrap())
-                        .max_by(|x, y| x.partial_cmp(y).unwrap())
+                        .map(|o| o.selection_fitness().unwrap())
+                        .max_by(|x, y| x.total_cmp(y))
                         .unwrap(),
                     FitnessStrategy::Mean => {
                         self.organisms
                             .iter()
-                            .map(|o| o.fitness.unwrap())
+                            .map(|o| o.selection_fitness().unwrap())
                             .sum::<f64>()
                             / self.organisms.len() as f64
                     }
@@ -109,16 +192,19 @@ impl Species {
     }
 }
 
-impl Into<PrintableSpecies> for &Species {
-    fn into(self) -> PrintableSpecies {
+impl From<&Species> for PrintableSpecies {
+    fn from(species: &Species) -> Self {
         let mut printable = PrintableSpecies {
-            representative: self.representative.as_ref().into(),
-            organisms: Vec::with_capacity(self.organisms.len()),
-            fitness: self.try_adjusted_fitness(),
-            id: self.id,
+            representative: species.representative.as_ref().into(),
+            organisms: Vec::with_capacity(species.organisms.len()),
+            fitness: species.try_adjusted_fitness(),
+            id: species.id,
+            best_fitness: species.best_fitness,
+            last_improvement: species.last_improvement,
+            created: species.created,
         };
 
-        for organism in &self.organisms {
+        for organism in &species.organisms {
             printable.organisms.push(organism.as_ref().into());
         }
 
@@ -127,32 +213,107 @@ impl Into<PrintableSpecies> for &Species {
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SpeciesConfig {
     pub representative: ReprentativeSelection, // Wie der Representative einer Spezies ausgewählt werden soll
     pub fitness: FitnessStrategy, // Wie die Fitness einer Spezies berechnet werden soll
-    pub species_distance_tolerance: f64, // Maximaler Abstande der Genome inenrhalb eines Species zum Representative
+    pub species_distance_tolerance: f64, // Maximaler Abstande der Genome inenrhalb eines Species zum Representative (Startwert, falls dynamic_tolerance gesetzt ist)
+    pub dynamic_tolerance: Option<DynamicToleranceConfig>, // Passt species_distance_tolerance an, um eine Anzahl an Spezies zu erreichen
+    pub stagnation_limit: u32, // Nach so vielen Generationen ohne Verbesserung wird eine Spezies entfernt (0 entspricht nie)
+    pub protected_species_count: usize, // So viele Spezies mit der besten Fitness werden nie wegen Stagnation entfernt
 }
 
-impl SpeciesConfig {
-    pub fn validate(&self) -> Result<(), String> {
-        config_util::assert_not_negative(
+impl Default for SpeciesConfig {
+    fn default() -> Self {
+        SpeciesConfig {
+            representative: ReprentativeSelection::Random,
+            fitness: FitnessStrategy::Mean,
+            species_distance_tolerance: 3.0,
+            dynamic_tolerance: None,
+            stagnation_limit: 15,
+            protected_species_count: 2,
+        }
+    }
+}
+
+impl Validate for SpeciesConfig {
+    fn check(&self, validation: &mut Validation) {
+        if self.stagnation_limit != 0 && self.protected_species_count == 0 {
+            validation.error(
+                "protected_species_count",
+                "must be at least 1 if stagnation_limit is set",
+            );
+        }
+        validation.not_negative(
             self.species_distance_tolerance,
             "species_distance_tolerance",
-        )
+        );
+        if self.species_distance_tolerance == 0.0 && self.dynamic_tolerance.is_none() {
+            validation.warning(
+                "species_distance_tolerance",
+                "is 0, only identical genomes will share a species",
+            );
+        }
+        validation.check("dynamic_tolerance", &self.dynamic_tolerance);
     }
 }
 
-// Strategie um den Representative einer Spezies auszuwählen
 #[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DynamicToleranceConfig {
+    pub target_species_count: usize, // Diese Anzahl an Spezies wird angestrebt
+    pub step: f64, // Um diesen Wert wird die Toleranz pro Generation erhöht bzw. verringert
+    pub min_tolerance: f64, // Untere Grenze der Toleranz
+    pub max_tolerance: f64, // Obere Grenze der Toleranz
+}
+
+impl Default for DynamicToleranceConfig {
+    fn default() -> Self {
+        DynamicToleranceConfig {
+            target_species_count: 10,
+            step: 0.1,
+            min_tolerance: 0.5,
+            max_tolerance: 10.0,
+        }
+    }
+}
+
+impl Validate for DynamicToleranceConfig {
+    fn check(&self, validation: &mut Validation) {
+        validation.not_zero(self.target_species_count, "target_species_count");
+        if self.min_tolerance > self.max_tolerance {
+            validation.error("min_tolerance", "must not be greater than max_tolerance");
+        }
+        validation.not_negative(self.step, "step");
+        validation.not_negative(self.min_tolerance, "min_tolerance");
+    }
+}
+
+impl DynamicToleranceConfig {
+    // Passt die Toleranz an die aktuelle Anzahl an Spezies an
+    pub(crate) fn adjust(&self, tolerance: f64, species_count: usize) -> f64 {
+        let tolerance = match species_count.cmp(&self.target_species_count) {
+            Ordering::Less => tolerance - self.step,
+            Ordering::Greater => tolerance + self.step,
+            Ordering::Equal => tolerance,
+        };
+        tolerance.clamp(self.min_tolerance, self.max_tolerance)
+    }
+}
+
+// Strategie um den Representative einer Spezies auszuwählen
+#[derive(Serialize, Deserialize, Default)]
 pub enum ReprentativeSelection {
     First, // Erster Organismus (nicht zufällig, aber auch nicht deterministisch)
+    #[default]
     Random, // Zufälliger Organismus
            // TODO: CLOSEST     // Organisumus am nächsten zum alten Repräsentanten
 }
 
 // Strategie um die Fitness einer Spezies zu berechnen
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub enum FitnessStrategy {
+    #[default]
     Mean, // Mittelwert aller Organismen in der Spezies
     Best, // Bester Organismus in der Spezies
 }
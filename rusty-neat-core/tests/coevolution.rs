@@ -0,0 +1,156 @@
+mod common;
+
+use std::{collections::HashMap, sync::Mutex};
+
+use rand::{rngs::StdRng, SeedableRng};
+use rusty_neat_core::{
+    coevolution::{Coevolution, CoevolutionConfig, Rating, Schedule},
+    gene_pool::GenePool,
+    organism::Organism,
+    population::Population,
+};
+
+// Organismen einer zufälligen Generation 0
+fn organisms(count: usize) -> Vec<Organism> {
+    let dir = common::test_dir(&format!("coevolution_{}", count));
+    let config = common::write_config_with(&dir, 17, 10, |config| {
+        config["reproduction"]["organism_count"] = count.into();
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    population.organisms().to_vec()
+}
+
+fn strength(organism: &Organism) -> f64 {
+    organism.evaluate(&[1.0, 1.0]).unwrap()[0]
+}
+
+// Der stärkere Organismus gewinnt immer
+fn game(first: &Organism, second: &Organism) -> (f64, f64) {
+    let (first, second) = (strength(first), strength(second));
+    if first > second {
+        (1.0, 0.0)
+    } else if first < second {
+        (0.0, 1.0)
+    } else {
+        (0.5, 0.5)
+    }
+}
+
+fn evaluate(schedule: Schedule, rating: Rating, organisms: &mut [Organism]) -> Coevolution {
+    let mut coevolution = Coevolution::new(CoevolutionConfig { schedule, rating }).unwrap();
+    coevolution.evaluate(organisms, game, &mut StdRng::seed_from_u64(5));
+    coevolution
+}
+
+// Prüft, dass die Fitness mit der Stärke steigt
+fn assert_ordered_by_strength(organisms: &[Organism]) {
+    let mut sorted: Vec<&Organism> = organisms.iter().collect();
+    sorted.sort_by(|a, b| strength(a).partial_cmp(&strength(b)).unwrap());
+    for pair in sorted.windows(2) {
+        if strength(pair[0]) < strength(pair[1]) {
+            assert!(pair[0].fitness.unwrap() < pair[1].fitness.unwrap());
+        }
+    }
+}
+
+fn strongest(organisms: &[Organism]) -> &Organism {
+    organisms
+        .iter()
+        .max_by(|a, b| strength(a).partial_cmp(&strength(b)).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn round_robin_ratings_follow_strength() {
+    let mut organisms = organisms(30);
+
+    evaluate(Schedule::RoundRobin, Rating::Score, &mut organisms);
+    assert_ordered_by_strength(&organisms);
+    assert!(organisms
+        .iter()
+        .all(|o| (0.0..=1.0).contains(&o.fitness.unwrap())));
+
+    evaluate(Schedule::RoundRobin, Rating::Glicko, &mut organisms);
+    assert_ordered_by_strength(&organisms);
+
+    evaluate(
+        Schedule::RoundRobin,
+        Rating::Elo { k: 16.0 },
+        &mut organisms,
+    );
+    let best = organisms.iter().max().unwrap();
+    assert_eq!(strength(best), strength(strongest(&organisms)));
+    assert!(best.fitness.unwrap() > 1500.0);
+}
+
+#[test]
+fn swiss_and_random_schedules_find_the_strongest() {
+    let mut organisms = organisms(32);
+
+    evaluate(Schedule::Swiss { rounds: 6 }, Rating::Score, &mut organisms);
+    assert_eq!(organisms.iter().max().unwrap().fitness, Some(1.0));
+    assert_eq!(strongest(&organisms).fitness, Some(1.0));
+
+    evaluate(
+        Schedule::RandomOpponents { count: 10 },
+        Rating::Score,
+        &mut organisms,
+    );
+    assert_eq!(strongest(&organisms).fitness, Some(1.0));
+}
+
+#[test]
+fn swiss_byes_rotate_with_an_odd_number_of_organisms() {
+    let mut organisms = organisms(11);
+    let games: Mutex<HashMap<u64, usize>> = Mutex::new(HashMap::new());
+    let counting_game = |first: &Organism, second: &Organism| {
+        let mut games = games.lock().unwrap();
+        *games.entry(first.genome().id()).or_default() += 1;
+        *games.entry(second.genome().id()).or_default() += 1;
+        game(first, second)
+    };
+    let config = CoevolutionConfig {
+        schedule: Schedule::Swiss { rounds: 22 },
+        rating: Rating::Score,
+    };
+    let mut coevolution = Coevolution::new(config).unwrap();
+    coevolution.evaluate(&mut organisms, counting_game, &mut StdRng::seed_from_u64(5));
+
+    // Jeder Organismus setzt genau zwei der 22 Runden aus, auch der schwächste
+    let games = games.into_inner().unwrap();
+    assert_eq!(games.len(), 11);
+    assert!(games.values().all(|count| *count == 20));
+}
+
+#[test]
+fn hall_of_fame_keeps_the_best_organisms() {
+    let mut organisms = organisms(20);
+    let mut coevolution = Coevolution::new(CoevolutionConfig {
+        schedule: Schedule::HallOfFame { size: 3 },
+        rating: Rating::Score,
+    })
+    .unwrap();
+    let mut rng = StdRng::seed_from_u64(9);
+
+    for generation in 1..=5 {
+        coevolution.evaluate(&mut organisms, game, &mut rng);
+        assert_eq!(coevolution.hall_of_fame().len(), generation.min(3));
+    }
+    // Der stärkste Organismus gewinnt gegen alle Mitglieder der Hall of Fame (oder spielt unentschieden)
+    assert!(strongest(&organisms).fitness.unwrap() >= 0.5);
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let invalid = [
+        (Schedule::RandomOpponents { count: 0 }, Rating::Score),
+        (Schedule::Swiss { rounds: 0 }, Rating::Score),
+        (Schedule::HallOfFame { size: 0 }, Rating::Score),
+        (Schedule::RoundRobin, Rating::Elo { k: -1.0 }),
+    ];
+    for (schedule, rating) in invalid {
+        assert!(Coevolution::new(CoevolutionConfig { schedule, rating }).is_err());
+    }
+}
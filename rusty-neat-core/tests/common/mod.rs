@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rusty_neat_core::organism::Organism;
+
+pub fn xor_score(organism: &Organism) -> f64 {
+    let cases = [
+        ([0.0, 0.0], 0.0),
+        ([1.0, 0.0], 1.0),
+        ([0.0, 1.0], 1.0),
+        ([1.0, 1.0], 0.0),
+    ];
+    let error: f64 = cases
+        .iter()
+        .map(|(input, expected)| (organism.evaluate(input).unwrap()[0] - expected).powi(2))
+        .sum();
+    4.0 - error
+}
+
+pub fn xor_fitness(organisms: &mut [Organism]) {
+    for organism in organisms {
+        organism.fitness = Some(xor_score(organism));
+    }
+}
+
+// Ein leeres Verzeichnis im temporären Verzeichnis des Systems
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("rusty-neat-tests").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Die Namen und Inhalte aller Dateien eines Verzeichnisses, sortiert nach dem Namen
+pub fn read_files(dir: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+    let mut files: Vec<(PathBuf, Vec<u8>)> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let content = fs::read(&path).unwrap();
+            (PathBuf::from(path.file_name().unwrap()), content)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+// Schreibt eine verkleinerte Variante der config.json des Repositories mit dem gegebenen Seed
+pub fn write_config(dir: &Path, seed: u64, max_generations: u32) -> PathBuf {
+    write_config_with(dir, seed, max_generations, |_| {})
+}
+
+// Wie write_config, die Konfiguration kann aber vor dem Schreiben angepasst werden
+pub fn write_config_with<F: FnOnce(&mut serde_json::Value)>(
+    dir: &Path,
+    seed: u64,
+    max_generations: u32,
+    adjust: F,
+) -> PathBuf {
+    let mut config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("../config.json").unwrap()).unwrap();
+    config["seed"] = seed.into();
+    config["max_generations"] = max_generations.into();
+    config["target_fitness"] = 4.0.into();
+    config["reproduction"]["organism_count"] = 150.into();
+    adjust(&mut config);
+
+    let path = dir.join("config.json");
+    fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+    path
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use std::fs;
+
+use rusty_neat_core::{
+    config::ConfigLoader,
+    error::NeatError,
+    gene_pool::{GenePool, InitialConnections},
+    genome::NewConnectionWeight,
+    population::{Population, PopulationConfig},
+    reproduction::TierCondition,
+    species::ReprentativeSelection,
+};
+
+fn invalid_field(result: Result<PopulationConfig, NeatError>) -> String {
+    match result {
+        Err(NeatError::InvalidConfig(issues)) => issues[0].field.clone(),
+        Err(err) => panic!("expected an invalid config error, got {}", err),
+        Ok(_) => panic!("expected an invalid config error"),
+    }
+}
+
+#[test]
+fn every_field_has_a_default() {
+    let config = ConfigLoader::new().load().unwrap();
+    assert_eq!(config.reproduction.mutation_tiers.len(), 1);
+    assert!(config.novelty.is_none());
+
+    let config = PopulationConfig {
+        max_generations: 2,
+        seed: Some(1),
+        ..PopulationConfig::default()
+    };
+    let mut population = Population::with_config(GenePool::new_dense(2, 1), config).unwrap();
+    population.evolve(common::xor_fitness).unwrap();
+    assert_eq!(population.generation(), 2);
+}
+
+#[test]
+fn layers_are_merged_in_order() {
+    let dir = common::test_dir("config_layers");
+    let base = common::write_config(&dir, 5, 10);
+    let toml = dir.join("profile.toml");
+    fs::write(
+        &toml,
+        r#"
+max_generations = 20
+
+[species]
+representative = "First"
+
+[[reproduction.mutation_tiers]]
+condition = "Always"
+config = { add_node_prob = 0.5 }
+"#,
+    )
+    .unwrap();
+    let yaml = dir.join("local.yaml");
+    fs::write(
+        &yaml,
+        "max_generations: 30\ninitial_topology:\n  hidden_nodes: 2\n",
+    )
+    .unwrap();
+
+    let config = ConfigLoader::new()
+        .file(&base)
+        .and_then(|loader| loader.file(&toml))
+        .and_then(|loader| loader.file(&yaml))
+        .and_then(|loader| loader.optional_file(&dir.join("missing.json")))
+        .and_then(|loader| loader.load())
+        .unwrap();
+    assert_eq!(config.max_generations, 30);
+    assert_eq!(config.seed, Some(5));
+    assert_eq!(config.initial_topology.hidden_nodes, 2);
+    assert!(matches!(
+        config.initial_topology.connections,
+        InitialConnections::Full
+    ));
+    // Objekte werden zusammengeführt, die übrigen Werte der Spezies stammen aus der Basis
+    assert!(matches!(
+        config.species.representative,
+        ReprentativeSelection::First
+    ));
+    assert_eq!(config.species.species_distance_tolerance, 3.0);
+    // Arrays werden ersetzt, fehlende Werte der Stufe erhalten ihren Standardwert
+    assert_eq!(config.reproduction.mutation_tiers.len(), 1);
+    assert_eq!(
+        config.reproduction.mutation_tiers[0].config.add_node_prob,
+        0.5
+    );
+    assert_eq!(
+        config.reproduction.mutation_tiers[0]
+            .config
+            .change_weight_prob,
+        0.8
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn overrides_from_environment_and_arguments() {
+    let dir = common::test_dir("config_overrides");
+    let base = common::write_config(&dir, 5, 10);
+    let vars = [
+        ("NEAT_REPRODUCTION__ORGANISM_COUNT", "42"),
+        ("NEAT_SPECIES__REPRESENTATIVE", "First"),
+        (
+            "NEAT_REPRODUCTION__MUTATION_TIERS__0__CONDITION",
+            r#"{"MinSpeciesAge": 3}"#,
+        ),
+        (
+            "NEAT_REPRODUCTION__MUTATION_TIERS__1__CONFIG__ADD_NODE_PROB",
+            "0.2",
+        ),
+        // Varianten behalten ihre Schreibweise
+        ("NEAT_INITIAL_ORGANISM_WEIGHT__Random__STD_DEV", "1.5"),
+        ("PATH", "/usr/bin"),
+    ]
+    .iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()));
+
+    let config = ConfigLoader::new()
+        .file(&base)
+        .and_then(|loader| loader.env_vars(vars))
+        .and_then(|loader| {
+            loader.args(vec![
+                "seed=7".to_owned(),
+                "reproduction.organism_count=50".to_owned(),
+                "novelty.nearest_neighbours=3".to_owned(),
+                "novelty.fitness_weight=0.5".to_owned(),
+                "novelty.archive_insertion={\"Random\":{\"probability\":0.1}}".to_owned(),
+                "novelty.max_archive_size=10".to_owned(),
+            ])
+        })
+        .and_then(|loader| loader.load())
+        .unwrap();
+    assert_eq!(config.seed, Some(7));
+    // Spätere Überschreibungen gewinnen
+    assert_eq!(config.reproduction.organism_count, 50);
+    assert!(matches!(
+        config.species.representative,
+        ReprentativeSelection::First
+    ));
+    assert!(matches!(
+        config.reproduction.mutation_tiers[0].condition,
+        TierCondition::MinSpeciesAge(3)
+    ));
+    assert_eq!(
+        config.reproduction.mutation_tiers[1].config.add_node_prob,
+        0.2
+    );
+    match config.initial_organism_weight {
+        NewConnectionWeight::Random(distribution) => assert_eq!(distribution.std_dev, 1.5),
+        NewConnectionWeight::Fixed(_) => panic!("expected a random weight"),
+    }
+    // Ein nicht gesetzter optionaler Abschnitt wird durch die Überschreibungen erzeugt
+    assert_eq!(config.novelty.unwrap().nearest_neighbours, 3);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn errors_name_the_dotted_path() {
+    let dir = common::test_dir("config_errors");
+    let base = common::write_config(&dir, 5, 10);
+    let load = |key: &str, value: &str| {
+        ConfigLoader::new()
+            .file(&base)
+            .and_then(|loader| loader.set(key, value))
+            .and_then(|loader| loader.load())
+    };
+
+    // Falscher Typ
+    assert_eq!(
+        invalid_field(load(
+            "reproduction.mutation_tiers[1].config.add_node_prob",
+            "many"
+        )),
+        "reproduction.mutation_tiers[1].config.add_node_prob"
+    );
+    // Ungültiger Wert
+    assert_eq!(
+        invalid_field(load(
+            "reproduction.mutation_tiers[0].config.add_node_prob",
+            "2.0"
+        )),
+        "reproduction.mutation_tiers[0].config.add_node_prob"
+    );
+    // Unbekanntes Feld
+    assert!(invalid_field(load("species.stagnaton_limit", "3")).starts_with("species"));
+    assert!(invalid_field(load("reproduction.annealing.decy", "0.9")).starts_with("reproduction"));
+    // Fehlende Felder optionaler Abschnitte haben Standardwerte
+    let config = load("reproduction.annealing.decay", "0.9").unwrap();
+    let annealing = config.reproduction.annealing.as_ref().unwrap();
+    assert_eq!(annealing.decay, 0.9);
+    assert_eq!(annealing.min_factor, 0.1);
+    // Nicht vorhandenes Element
+    assert!(load("reproduction.mutation_tiers[5].config.add_node_prob", "0.1").is_err());
+    assert!(load("reproduction..kill_ratio", "0.1").is_err());
+    assert!(ConfigLoader::new().args(vec!["seed".to_owned()]).is_err());
+
+    let ini = dir.join("config.ini");
+    fs::write(&ini, "").unwrap();
+    assert!(matches!(
+        ConfigLoader::new().file(&ini),
+        Err(NeatError::UnknownFileExtension(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
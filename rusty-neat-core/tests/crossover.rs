@@ -0,0 +1,213 @@
+mod common;
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+use rusty_neat_core::{
+    error::NeatError,
+    gene_pool::GenePool,
+    genome::{CrossoverConfig, CrossoverOperator, CrossoverWeightStrategy, Genome},
+    population::Population,
+};
+use rusty_neat_interchange::genome::PrintableGenome;
+
+// Eine Population nach einigen Generationen mit vielen strukturellen Mutationen, damit sich die Genomes
+// deutlich unterscheiden
+fn evolved_population(name: &str, crossover: serde_json::Value) -> Population {
+    let dir = common::test_dir(name);
+    let config = common::write_config_with(&dir, 13, 10, |config| {
+        config["reproduction"]["crossover"] = crossover;
+        for tier in config["reproduction"]["mutation_tiers"]
+            .as_array_mut()
+            .unwrap()
+        {
+            tier["config"]["add_node_prob"] = 0.3.into();
+            tier["config"]["add_connection_prob"] = 0.3.into();
+        }
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+    for _ in 0..5 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+        assert_eq!(population.organisms().len(), 150);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+    population
+}
+
+fn innovations(genome: &Genome) -> BTreeSet<u64> {
+    PrintableGenome::from(genome)
+        .connections
+        .iter()
+        .map(|c| c.innovation)
+        .collect()
+}
+
+fn weights(genome: &Genome) -> HashMap<u64, f64> {
+    PrintableGenome::from(genome)
+        .connections
+        .iter()
+        .map(|c| (c.innovation, c.weight))
+        .collect()
+}
+
+// Jede Innovation kommt höchstens einmal vor, stammt von einem Elternteil und verbindet Nodes des Nachkommen
+fn assert_aligned(offspring: &Genome, parents: &[&Genome], pool: &GenePool) {
+    let printable = PrintableGenome::from(offspring);
+    let nodes: HashSet<u64> = printable.nodes.iter().map(|n| n.id).collect();
+    assert_eq!(nodes.len(), printable.nodes.len());
+    let parent_innovations: BTreeSet<u64> = parents.iter().flat_map(|p| innovations(p)).collect();
+
+    let mut seen = HashSet::new();
+    for connection in &printable.connections {
+        assert!(seen.insert(connection.innovation));
+        assert!(parent_innovations.contains(&connection.innovation));
+        let gene = pool.connections[connection.innovation as usize];
+        assert_eq!(gene.innovation as u64, connection.innovation);
+        assert!(nodes.contains(&(gene.from as u64)));
+        assert!(nodes.contains(&(gene.to as u64)));
+    }
+    assert_eq!(offspring.connection_count(), seen.len());
+}
+
+fn crossover(
+    parents: &[(&Genome, f64)],
+    pool: &GenePool,
+    config: &CrossoverConfig,
+    rng: &mut StdRng,
+) -> Genome {
+    let offspring = Genome::crossover(parents, pool, config, 0, 0, rng).unwrap();
+    let genomes: Vec<&Genome> = parents.iter().map(|(genome, _)| *genome).collect();
+    assert_aligned(&offspring, &genomes, pool);
+    offspring
+}
+
+#[test]
+fn two_parent_operators() {
+    let population = evolved_population("crossover_two_parents", serde_json::json!({}));
+    let pool = population.pool();
+    let organisms = population.organisms();
+    let mut rng = StdRng::seed_from_u64(2);
+
+    let mut checked = 0;
+    for pair in organisms.chunks(2) {
+        let (a, b) = (pair[0].genome(), pair[1].genome());
+        if innovations(a) == innovations(b) {
+            continue;
+        }
+        checked += 1;
+        let union: BTreeSet<u64> = innovations(a).union(&innovations(b)).copied().collect();
+
+        let config = CrossoverConfig::default();
+        let offspring = crossover(&[(a, 1.0), (b, 2.0)], pool, &config, &mut rng);
+        assert_eq!(innovations(&offspring), union);
+
+        // Nur der bessere Elternteil gibt seine disjunkten Gene weiter, bei gleicher Fitness beide
+        let config = CrossoverConfig {
+            operator: CrossoverOperator::Fitter,
+            weight_strategy: CrossoverWeightStrategy::Better,
+            ..CrossoverConfig::default()
+        };
+        let offspring = crossover(&[(a, 1.0), (b, 2.0)], pool, &config, &mut rng);
+        assert_eq!(innovations(&offspring), innovations(b));
+        assert_eq!(weights(&offspring), weights(b));
+        let offspring = crossover(&[(a, 3.0), (b, 3.0)], pool, &config, &mut rng);
+        assert_eq!(innovations(&offspring), union);
+
+        // Bei BLX-α mit alpha 0 liegen gemeinsame Gewichte zwischen denen der Eltern
+        let config = CrossoverConfig {
+            weight_strategy: CrossoverWeightStrategy::Blend { alpha: 0.0 },
+            ..CrossoverConfig::default()
+        };
+        let offspring = crossover(&[(a, 1.0), (b, 2.0)], pool, &config, &mut rng);
+        let (weights_a, weights_b) = (weights(a), weights(b));
+        for (innovation, weight) in weights(&offspring) {
+            match (weights_a.get(&innovation), weights_b.get(&innovation)) {
+                (Some(x), Some(y)) => assert!(x.min(*y) <= weight && weight <= x.max(*y)),
+                (Some(x), None) | (None, Some(x)) => assert_eq!(weight, *x),
+                (None, None) => unreachable!(),
+            }
+        }
+    }
+    assert!(checked > 10);
+    assert!(Genome::crossover(&[], pool, &CrossoverConfig::default(), 0, 0, &mut rng).is_none());
+}
+
+#[test]
+fn multi_parent_crossover() {
+    let population = evolved_population("crossover_multi_parent", serde_json::json!({}));
+    let pool = population.pool();
+    let config = CrossoverConfig {
+        operator: CrossoverOperator::MultiParent { parents: 4 },
+        weight_strategy: CrossoverWeightStrategy::Random,
+        ..CrossoverConfig::default()
+    };
+    let mut rng = StdRng::seed_from_u64(3);
+
+    for group in population.organisms().chunks_exact(4) {
+        let parents: Vec<(&Genome, f64)> = group
+            .iter()
+            .enumerate()
+            .map(|(i, organism)| (organism.genome(), i as f64))
+            .collect();
+        // Die Struktur stammt vom besten Elternteil, jedes Gewicht von einem der Eltern mit diesem Gen
+        let offspring = crossover(&parents, pool, &config, &mut rng);
+        assert_eq!(innovations(&offspring), innovations(parents[3].0));
+        for (innovation, weight) in weights(&offspring) {
+            assert!(parents
+                .iter()
+                .any(|(genome, _)| weights(genome).get(&innovation) == Some(&weight)));
+        }
+
+        // Bei gleicher Fitness geben alle Eltern ihre Gene weiter
+        let equal: Vec<(&Genome, f64)> = parents.iter().map(|(g, _)| (*g, 1.0)).collect();
+        let offspring = crossover(&equal, pool, &config, &mut rng);
+        let all: BTreeSet<u64> = parents.iter().flat_map(|(g, _)| innovations(g)).collect();
+        assert_eq!(innovations(&offspring), all);
+    }
+}
+
+#[test]
+fn operators_in_evolution() {
+    for (name, crossover) in [
+        (
+            "crossover_evolution_multi",
+            serde_json::json!({"operator": {"MultiParent": {"parents": 3}}}),
+        ),
+        (
+            "crossover_evolution_blend",
+            serde_json::json!({"operator": "Fitter", "weight_strategy": {"Blend": {"alpha": 0.5}}}),
+        ),
+    ] {
+        let population = evolved_population(name, crossover);
+        for organism in population.organisms() {
+            assert_aligned(organism.genome(), &[organism.genome()], population.pool());
+        }
+    }
+
+    let dir = common::test_dir("crossover_invalid");
+    let config = common::write_config_with(&dir, 13, 10, |config| {
+        config["reproduction"]["crossover"] = serde_json::json!({
+            "operator": {"MultiParent": {"parents": 1}},
+            "weight_strategy": {"Blend": {"alpha": -0.5}}
+        });
+    });
+    match Population::new(GenePool::new_dense(2, 1), &config) {
+        Err(NeatError::InvalidConfig(issues)) => {
+            let fields: Vec<&str> = issues.iter().map(|issue| issue.field.as_str()).collect();
+            assert_eq!(
+                fields,
+                [
+                    "reproduction.crossover.weight_strategy.alpha",
+                    "reproduction.crossover.operator.parents"
+                ]
+            );
+        }
+        _ => panic!("expected an invalid config error"),
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
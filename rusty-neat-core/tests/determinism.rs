@@ -0,0 +1,63 @@
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rusty_neat_core::{gene_pool::GenePool, population::Population};
+use rusty_neat_interchange::io::FileType;
+
+fn run(dir: &Path, seed: u64) -> PathBuf {
+    fs::create_dir_all(dir).unwrap();
+    let config = common::write_config(dir, seed, 8);
+    let target = dir.join("population");
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population
+        .write_generations(&target, FileType::Bincode)
+        .unwrap();
+    population.evolve(common::xor_fitness).unwrap();
+    target
+}
+
+#[test]
+fn same_seed_gives_identical_generations() {
+    let dir = common::test_dir("determinism");
+
+    let first = common::read_files(&run(&dir.join("first"), 42));
+    let second = common::read_files(&run(&dir.join("second"), 42));
+    let other = common::read_files(&run(&dir.join("other"), 43));
+
+    assert!(first.len() > 1);
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn parallel_evaluation_matches_sequential_evaluation() {
+    let dir = common::test_dir("parallel");
+    let config = common::write_config(&dir, 21, 6);
+
+    let sequential_target = dir.join("sequential");
+    let mut sequential = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    sequential
+        .write_generations(&sequential_target, FileType::Bincode)
+        .unwrap();
+    sequential.evolve(common::xor_fitness).unwrap();
+
+    let parallel_target = dir.join("parallel");
+    let mut parallel = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    parallel
+        .write_generations(&parallel_target, FileType::Bincode)
+        .unwrap();
+    parallel.evolve_parallel(common::xor_score).unwrap();
+
+    assert_eq!(
+        common::read_files(&sequential_target),
+        common::read_files(&parallel_target)
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
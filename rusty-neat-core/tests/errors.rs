@@ -0,0 +1,148 @@
+mod common;
+
+use std::{cmp::Ordering, error::Error, fs};
+
+use rusty_neat_core::{
+    error::NeatError, gene_pool::GenePool, genome::EvaluationConfig, population::Population,
+};
+use rusty_neat_interchange::generation::{self, PrintableGeneration};
+
+#[test]
+fn invalid_config_names_the_field() {
+    let dir = common::test_dir("errors_config");
+    let config = common::write_config_with(&dir, 3, 10, |config| {
+        config["reproduction"]["mutation_tiers"][1]["config"]["add_node_prob"] = 1.5.into();
+    });
+    match Population::new(GenePool::new_dense(2, 1), &config) {
+        Err(NeatError::InvalidConfig(issues)) => {
+            assert_eq!(
+                issues[0].field,
+                "reproduction.mutation_tiers[1].config.add_node_prob"
+            )
+        }
+        _ => panic!("expected an invalid config error"),
+    }
+
+    let config = common::write_config_with(&dir, 3, 10, |config| {
+        config["target_fitness"] = (-1.0).into();
+    });
+    match Population::new(GenePool::new_dense(2, 1), &config) {
+        Err(NeatError::InvalidConfig(issues)) => assert_eq!(issues[0].field, "target_fitness"),
+        _ => panic!("expected an invalid config error"),
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_files_and_unknown_extensions_are_errors() {
+    let dir = common::test_dir("errors_files");
+
+    let missing = Population::new(GenePool::new_dense(2, 1), &dir.join("missing.json"));
+    match missing {
+        Err(err @ NeatError::Io { .. }) => assert!(err.source().is_some()),
+        _ => panic!("expected an io error"),
+    }
+
+    for name in ["gen-0.txt", "gen-0"] {
+        let path = dir.join(name);
+        fs::write(&path, "").unwrap();
+        let result: Result<PrintableGeneration, NeatError> = generation::read(&path);
+        assert!(matches!(result, Err(NeatError::UnknownFileExtension(_))));
+    }
+
+    let path = dir.join("gen-0.json");
+    fs::write(&path, "{").unwrap();
+    let result: Result<PrintableGeneration, NeatError> = generation::read(&path);
+    assert!(matches!(result, Err(NeatError::Serialization { .. })));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn evaluation_errors() {
+    let dir = common::test_dir("errors_evaluation");
+    let config = common::write_config(&dir, 3, 10);
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+
+    let organism = &population.organisms()[0];
+    assert!(matches!(
+        organism.evaluate(&[1.0]),
+        Err(NeatError::InputLength {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    assert!(matches!(
+        organism
+            .genome()
+            .clone()
+            .evaluate(&[1.0, 0.0, 1.0], &EvaluationConfig::default()),
+        Err(NeatError::InputLength {
+            expected: 2,
+            actual: 3
+        })
+    ));
+    let mut output = [0.0; 2];
+    assert!(matches!(
+        organism.network().evaluate(&[1.0, 0.0], &mut output),
+        Err(NeatError::OutputLength {
+            expected: 1,
+            actual: 2
+        })
+    ));
+    assert!(organism
+        .network()
+        .evaluate_batch(&[0.0, 0.0, 1.0], &mut output)
+        .is_err());
+
+    assert!(matches!(
+        population.finish_generation(),
+        Err(NeatError::MissingFitness)
+    ));
+    common::xor_fitness(population.organisms_mut());
+    population.finish_generation().unwrap();
+    assert!(matches!(
+        population.finish_generation(),
+        Err(NeatError::InvalidState(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn non_finite_fitness_is_an_error() {
+    let dir = common::test_dir("errors_not_finite");
+    let config = common::write_config(&dir, 3, 10);
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+
+    common::xor_fitness(population.organisms_mut());
+    population.organisms_mut()[3].fitness = Some(f64::NAN);
+    // Vergleiche der Fitness führen auch mit NaN nicht zu einem Panic
+    assert!(population.best().is_some());
+    population.organisms().iter().max().unwrap();
+    // Gleichheit und Ordnung stimmen überein
+    let organisms = population.organisms();
+    let (mut a, mut b) = (organisms[0].clone(), organisms[1].clone());
+    a.fitness = Some(f64::NAN);
+    b.fitness = Some(f64::NAN);
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert!(a == b);
+    a.fitness = Some(-0.0);
+    b.fitness = Some(0.0);
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert!(a != b);
+    assert!(matches!(
+        population.finish_generation(),
+        Err(NeatError::NotFinite("fitness"))
+    ));
+
+    population.organisms_mut()[3].fitness = Some(f64::INFINITY);
+    assert!(population.finish_generation().is_err());
+    population.organisms_mut()[3].fitness = Some(0.5);
+    population.finish_generation().unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+}
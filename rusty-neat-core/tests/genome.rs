@@ -0,0 +1,141 @@
+use rand::{rngs::StdRng, SeedableRng};
+use rusty_neat_core::{
+    activation::Activation,
+    gene_pool::GenePool,
+    genome::{EvaluationConfig, Genome, NewConnectionWeight},
+};
+use rusty_neat_interchange::genome::PrintableGenome;
+
+const ACTIVATIONS: [Activation; 13] = [
+    Activation::Identity,
+    Activation::Sigmoid,
+    Activation::Relu,
+    Activation::Tanh,
+    Activation::Gaussian,
+    Activation::Sin,
+    Activation::Abs,
+    Activation::Step,
+    Activation::Softplus,
+    Activation::Clamped,
+    Activation::ClampedRelu,
+    Activation::Cos,
+    Activation::Square,
+];
+
+// Ein Input, ein Output mit der Identität und für jede Aktivierungsfunktion eine Hidden-Node zwischen beiden
+fn genome_with_activations(biases: &[f64]) -> (Genome, GenePool) {
+    let mut pool = GenePool::new_dense(1, 1);
+    let mut genome = Genome::new(0, 0, 1, 1, Activation::Identity, 0.0);
+    let mut rng = StdRng::seed_from_u64(0);
+    for (i, (activation, bias)) in ACTIVATIONS.iter().zip(biases).enumerate() {
+        let hidden = pool.create_hidden_node(0.5, i as f64 / ACTIVATIONS.len() as f64);
+        genome.add_node(hidden, *activation, *bias);
+        for (from, to, weight) in [(0, hidden, 1.0), (hidden, 1, 10f64.powi(-(i as i32)))] {
+            let connection = pool.create_connection(from, to).unwrap();
+            genome.add_new_connection(connection, &NewConnectionWeight::Fixed(weight), &mut rng);
+        }
+    }
+    (genome, pool)
+}
+
+fn json(genome: &Genome) -> serde_json::Value {
+    serde_json::to_value(PrintableGenome::from(genome)).unwrap()
+}
+
+#[test]
+fn activation_functions() {
+    let cases: [(Activation, [f64; 3]); 12] = [
+        (Activation::Identity, [-2.0, 0.0, 0.5]),
+        (Activation::Relu, [0.0, 0.0, 0.5]),
+        (
+            Activation::Tanh,
+            [-0.9640275800758169, 0.0, 0.46211715726000974],
+        ),
+        (
+            Activation::Gaussian,
+            [0.01831563888873418, 1.0, 0.7788007830714049],
+        ),
+        (
+            Activation::Sin,
+            [-0.9092974268256817, 0.0, 0.479425538604203],
+        ),
+        (Activation::Abs, [2.0, 0.0, 0.5]),
+        (Activation::Step, [0.0, 0.0, 1.0]),
+        (
+            Activation::Softplus,
+            [
+                0.12692801104297263,
+                std::f64::consts::LN_2,
+                0.9740769841801067,
+            ],
+        ),
+        (Activation::Clamped, [-1.0, 0.0, 0.5]),
+        (Activation::ClampedRelu, [0.0, 0.0, 0.5]),
+        (
+            Activation::Cos,
+            [-0.4161468365471424, 1.0, 0.8775825618903728],
+        ),
+        (Activation::Square, [4.0, 0.0, 0.25]),
+    ];
+    for (activation, expected) in cases {
+        for (x, expected) in [-2.0, 0.0, 0.5].iter().zip(expected) {
+            let value = (activation.function())(*x);
+            assert!(
+                (value - expected).abs() < 1e-12,
+                "{:?}({}) = {}",
+                activation,
+                x,
+                value
+            );
+        }
+    }
+
+    // Softplus läuft auch für große Werte nicht über
+    assert_eq!((Activation::Softplus.function())(1000.0), 1000.0);
+    assert_eq!((Activation::Clamped.function())(3.0), 1.0);
+    assert_eq!((Activation::ClampedRelu.function())(3.0), 1.0);
+}
+
+#[test]
+fn activations_round_trip_through_printable_genome() {
+    let (mut genome, pool) = genome_with_activations(&[0.0; 13]);
+    let printable = PrintableGenome::from(&genome);
+    for (node, activation) in printable.nodes[2..].iter().zip(ACTIVATIONS) {
+        assert_eq!(Activation::from(&node.activation), activation);
+    }
+
+    let mut restored = Genome::from_printable(&printable, &pool);
+    assert_eq!(json(&restored), json(&genome));
+    let config = EvaluationConfig::default();
+    for input in [-2.0, 0.0, 0.5] {
+        assert_eq!(
+            restored.evaluate(&[input], &config).unwrap(),
+            genome.evaluate(&[input], &config).unwrap()
+        );
+    }
+}
+
+#[test]
+fn biases_round_trip_through_printable_genome() {
+    let biases: Vec<f64> = (0..ACTIVATIONS.len())
+        .map(|i| i as f64 * 0.25 - 1.0)
+        .collect();
+    let (mut genome, pool) = genome_with_activations(&biases);
+    let printable = PrintableGenome::from(&genome);
+    let printed: Vec<f64> = printable.nodes[2..].iter().map(|node| node.bias).collect();
+    assert_eq!(printed, biases);
+
+    let mut restored = Genome::from_printable(&printable, &pool);
+    assert_eq!(json(&restored), json(&genome));
+    let config = EvaluationConfig::default();
+    assert_eq!(
+        restored.evaluate(&[0.5], &config).unwrap(),
+        genome.evaluate(&[0.5], &config).unwrap()
+    );
+
+    // Der Bias wird vor der Aktivierungsfunktion addiert, auch bei Nodes ohne eingehende Connections
+    let mut genome = Genome::new(0, 0, 1, 1, Activation::Relu, 0.75);
+    assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [0.75]);
+    let mut genome = Genome::new(0, 0, 1, 1, Activation::Relu, -0.75);
+    assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [0.0]);
+}
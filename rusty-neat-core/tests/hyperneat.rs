@@ -0,0 +1,131 @@
+mod common;
+
+use rusty_neat_core::{
+    activation::Activation,
+    gene_pool::GenePool,
+    hyperneat::{
+        EvolvableSubstrateConfig, HyperNeat, HyperNeatConfig, Substrate, CPPN_OUTPUT_COUNT,
+    },
+    network::Network,
+    organism::Organism,
+    population::Population,
+};
+
+fn substrate() -> Substrate {
+    Substrate {
+        inputs: vec![vec![-1.0, -1.0], vec![0.0, -1.0], vec![1.0, -1.0]],
+        hidden: vec![Substrate::grid(3, 1)],
+        outputs: vec![vec![-0.5, 1.0], vec![0.5, 1.0]],
+    }
+}
+
+fn config(
+    weight_threshold: f64,
+    evolvable_substrate: Option<EvolvableSubstrateConfig>,
+) -> HyperNeatConfig {
+    HyperNeatConfig {
+        weight_threshold,
+        max_weight: 3.0,
+        hidden_activation: Activation::Tanh,
+        output_activation: Activation::Tanh,
+        evolvable_substrate,
+    }
+}
+
+fn evolvable(band_threshold: f64) -> EvolvableSubstrateConfig {
+    EvolvableSubstrateConfig {
+        initial_depth: 2,
+        max_depth: 4,
+        division_threshold: 0.01,
+        variance_threshold: 0.01,
+        band_threshold,
+        iteration_level: 1,
+    }
+}
+
+// CPPNs einer zufälligen Generation 0
+fn cppns(hyperneat: &HyperNeat, name: &str) -> Vec<Organism> {
+    let dir = common::test_dir(&format!("hyperneat_{}", name));
+    let config = common::write_config(&dir, 29, 10);
+    let mut population = Population::new(
+        GenePool::new_dense(hyperneat.cppn_input_count(), CPPN_OUTPUT_COUNT),
+        &config,
+    )
+    .unwrap();
+    population.next_generation().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    population.organisms()[..10].to_vec()
+}
+
+fn outputs(network: &Network, input: &[f64]) -> Vec<f64> {
+    let mut output = vec![0.0; network.output_count()];
+    network.evaluate(input, &mut output).unwrap();
+    output
+}
+
+fn depends_on_input(network: &Network) -> bool {
+    outputs(network, &[0.0, 0.0, 0.0]) != outputs(network, &[1.0, -1.0, 0.5])
+}
+
+#[test]
+fn invalid_substrates_are_rejected() {
+    let mut mixed = substrate();
+    mixed.outputs.push(vec![0.0, 1.0, 0.0]);
+    assert!(HyperNeat::new(mixed, config(0.2, None)).is_err());
+
+    let mut empty = substrate();
+    empty.outputs.clear();
+    assert!(HyperNeat::new(empty, config(0.2, None)).is_err());
+
+    // ES-HyperNEAT benötigt ein zweidimensionales Substrat
+    let three_dimensional = Substrate {
+        inputs: vec![vec![0.0, -1.0, 0.0]],
+        hidden: Vec::new(),
+        outputs: vec![vec![0.0, 1.0, 0.0]],
+    };
+    assert!(HyperNeat::new(three_dimensional, config(0.2, Some(evolvable(0.1)))).is_err());
+}
+
+#[test]
+fn layered_substrate_uses_cppn_weights() {
+    let hyperneat = HyperNeat::new(substrate(), config(0.0, None)).unwrap();
+    assert_eq!(hyperneat.cppn_input_count(), 4);
+
+    let networks: Vec<Network> = cppns(&hyperneat, "layered")
+        .iter()
+        .map(|c| hyperneat.build(c).unwrap())
+        .collect();
+    for network in &networks {
+        assert_eq!(network.input_count(), 3);
+        assert_eq!(network.output_count(), 2);
+    }
+    assert!(networks.iter().any(depends_on_input));
+
+    // Liegen alle Gewichte unter dem Schwellwert, hängen die Outputs nur vom Bias ab
+    let unconnected = HyperNeat::new(substrate(), config(1.0, None)).unwrap();
+    assert!(cppns(&unconnected, "unconnected")
+        .iter()
+        .all(|cppn| !depends_on_input(&unconnected.build(cppn).unwrap())));
+}
+
+#[test]
+fn evolvable_substrate_discovers_hidden_nodes() {
+    let hyperneat = HyperNeat::new(substrate(), config(0.0, Some(evolvable(0.0)))).unwrap();
+    let networks: Vec<Network> = cppns(&hyperneat, "evolvable")
+        .iter()
+        .map(|c| hyperneat.build(c).unwrap())
+        .collect();
+    for network in &networks {
+        assert_eq!(network.output_count(), 2);
+        assert!(outputs(network, &[1.0, 0.5, -1.0])
+            .iter()
+            .all(|o| o.is_finite()));
+    }
+    assert!(networks.iter().any(depends_on_input));
+
+    // Ohne ausgedrückte Connections gibt es keine Hidden-Nodes
+    let pruned = HyperNeat::new(substrate(), config(0.0, Some(evolvable(f64::MAX)))).unwrap();
+    assert!(cppns(&pruned, "pruned")
+        .iter()
+        .all(|cppn| !depends_on_input(&pruned.build(cppn).unwrap())));
+}
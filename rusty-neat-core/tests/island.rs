@@ -0,0 +1,197 @@
+mod common;
+
+use std::{collections::HashSet, fs};
+
+use rusty_neat_core::{
+    config::ConfigLoader,
+    error::NeatError,
+    gene_pool::GenePool,
+    island::{FullyConnected, IslandConfig, IslandModel, MigrationTopology, Ring},
+    population::PopulationConfig,
+};
+use rusty_neat_interchange::genome::PrintableGenome;
+
+fn load(dir_name: &str, seed: u64, max_generations: u32) -> PopulationConfig {
+    let dir = common::test_dir(dir_name);
+    let path = common::write_config(&dir, seed, max_generations);
+    let config = ConfigLoader::new().file(&path).unwrap().load().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    config
+}
+
+#[test]
+fn topologies() {
+    assert_eq!(Ring.targets(0, 3), [1]);
+    assert_eq!(Ring.targets(2, 3), [0]);
+    assert_eq!(FullyConnected.targets(1, 3), [0, 2]);
+    assert!(FullyConnected.targets(0, 1).is_empty());
+}
+
+#[test]
+fn best_organisms_migrate() {
+    let configs = (0..3).map(|_| load("island_migrate", 4, 10)).collect();
+    let config = IslandConfig {
+        migration_interval: 2,
+        migrant_count: 3,
+    };
+    let mut model = IslandModel::new(GenePool::new_dense(2, 1), configs, config, Ring).unwrap();
+
+    model.next_generation().unwrap();
+    // Mit verschiedenen Seeds unterscheiden sich die Inseln
+    assert_ne!(
+        model.islands()[0].organisms()[0].genome().id(),
+        model.islands()[1].organisms()[0].genome().id()
+    );
+    for generation in 1..=4 {
+        for island in 0..3 {
+            common::xor_fitness(model.organisms_mut(island));
+        }
+        let emigrants: Vec<HashSet<u64>> = model
+            .islands()
+            .iter()
+            .map(|island| {
+                let mut organisms: Vec<_> = island.organisms().iter().collect();
+                organisms.sort_by(|x, y| y.fitness.partial_cmp(&x.fitness).unwrap());
+                organisms.iter().take(3).map(|o| o.genome().id()).collect()
+            })
+            .collect();
+
+        model.next_generation().unwrap();
+        assert_eq!(model.generation(), generation);
+        for (index, island) in model.islands().iter().enumerate() {
+            assert_eq!(island.organisms().len(), 150);
+            // Jede Insel erhält die Besten ihrer Vorgängerin im Ring, die Migranten werden neu bewertet
+            if generation % 2 == 0 {
+                let unevaluated: HashSet<u64> = island
+                    .organisms()
+                    .iter()
+                    .filter(|o| o.fitness.is_none())
+                    .map(|o| o.genome().id())
+                    .collect();
+                assert!(emigrants[(index + 2) % 3].is_subset(&unevaluated));
+            }
+        }
+    }
+}
+
+#[test]
+fn migrants_do_not_replace_elites() {
+    // Ohne Migration bleiben die Inseln bis zur ersten Migration gleich, die übernommenen Eliten (die bereits
+    // eine Fitness haben) müssen danach auch mit vielen Migranten erhalten bleiben
+    let models: Vec<IslandModel> = [0, 100]
+        .iter()
+        .map(|migrant_count| {
+            let configs = (0..2).map(|_| load("island_elites", 3, 10)).collect();
+            let config = IslandConfig {
+                migration_interval: 2,
+                migrant_count: *migrant_count,
+            };
+            IslandModel::new(GenePool::new_dense(2, 1), configs, config, Ring).unwrap()
+        })
+        .collect();
+    let elites: Vec<Vec<HashSet<u64>>> = models
+        .into_iter()
+        .map(|mut model| {
+            model.next_generation().unwrap();
+            for _ in 0..2 {
+                for island in 0..2 {
+                    common::xor_fitness(model.organisms_mut(island));
+                }
+                model.next_generation().unwrap();
+            }
+            model
+                .islands()
+                .iter()
+                .map(|island| {
+                    island
+                        .organisms()
+                        .iter()
+                        .filter(|o| o.fitness.is_some())
+                        .map(|o| o.genome().id())
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+    assert!(elites[0].iter().all(|island| !island.is_empty()));
+    assert_eq!(elites[0], elites[1]);
+}
+
+#[test]
+fn islands_draw_different_genomes() {
+    // Ohne Migration hat keine Insel in einer Generation ein Genome, das auch auf einer anderen Insel vorkommt
+    let configs = (0..4).map(|_| load("island_streams", 1, 10)).collect();
+    let config = IslandConfig {
+        migration_interval: 1000,
+        migrant_count: 0,
+    };
+    let mut model = IslandModel::new(GenePool::new_dense(2, 1), configs, config, Ring).unwrap();
+    let mut genomes: Vec<HashSet<String>> = vec![HashSet::new(); 4];
+    model.next_generation().unwrap();
+    for _ in 0..4 {
+        for (island, genomes) in genomes.iter_mut().enumerate() {
+            genomes.extend(model.islands()[island].organisms().iter().map(|o| {
+                let printable = PrintableGenome::from(o.genome());
+                serde_json::to_string(&(printable.nodes, printable.connections)).unwrap()
+            }));
+            common::xor_fitness(model.organisms_mut(island));
+        }
+        model.next_generation().unwrap();
+    }
+    for i in 0..4 {
+        for j in i + 1..4 {
+            assert!(genomes[i].is_disjoint(&genomes[j]));
+        }
+    }
+}
+
+#[test]
+fn islands_evolve_until_one_reaches_the_target() {
+    let configs = (0..2).map(|_| load("island_evolve", 7, 50)).collect();
+    let mut model = IslandModel::new(
+        GenePool::new_dense(2, 1),
+        configs,
+        IslandConfig::default(),
+        FullyConnected,
+    )
+    .unwrap();
+    let best = model.evolve(common::xor_fitness).unwrap();
+    assert!(model.generation() <= 50);
+    assert_eq!(
+        best.fitness,
+        model
+            .islands()
+            .iter()
+            .filter_map(|island| island.best())
+            .map(|o| o.fitness)
+            .max_by(|x, y| x.partial_cmp(y).unwrap())
+            .unwrap()
+    );
+    assert!(model.pool().nodes.len() >= 3);
+}
+
+#[test]
+fn islands_must_be_compatible() {
+    let mut other = load("island_invalid", 1, 10);
+    other.initial_topology.hidden_nodes = 2;
+    let configs = vec![load("island_invalid", 1, 10), other];
+    let config = IslandConfig {
+        migration_interval: 0,
+        migrant_count: 1000,
+    };
+    match IslandModel::new(GenePool::new_dense(2, 1), configs, config, Ring) {
+        Err(NeatError::InvalidConfig(issues)) => {
+            let fields: Vec<&str> = issues.iter().map(|issue| issue.field.as_str()).collect();
+            assert_eq!(
+                fields,
+                [
+                    "migration_interval",
+                    "migrant_count",
+                    "islands[1].initial_topology.hidden_nodes",
+                    "migrant_count"
+                ]
+            );
+        }
+        _ => panic!("expected an invalid config error"),
+    }
+}
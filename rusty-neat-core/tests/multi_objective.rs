@@ -0,0 +1,126 @@
+mod common;
+
+use std::fs;
+
+use rusty_neat_core::{
+    gene_pool::GenePool,
+    multi_objective::{crowding_distances, non_dominated_fronts},
+    organism::Organism,
+    population::Population,
+};
+use rusty_neat_interchange::io::FileType;
+
+#[test]
+fn fronts_are_sorted_by_domination() {
+    let objectives = vec![
+        vec![1.0, 1.0],
+        vec![3.0, 1.0],
+        vec![2.0, 2.0],
+        vec![1.0, 3.0],
+        vec![0.0, 0.0],
+        vec![2.0, 2.0],
+    ];
+    let fronts = non_dominated_fronts(&objectives);
+    assert_eq!(fronts, vec![vec![1, 2, 3, 5], vec![0], vec![4]]);
+
+    let distances = crowding_distances(&objectives, &fronts[0]);
+    assert_eq!(distances[0], f64::INFINITY);
+    assert_eq!(distances[2], f64::INFINITY);
+    assert!(distances[1].is_finite() && distances[3].is_finite());
+
+    // Fronten mit höchstens zwei Punkten bestehen nur aus Randpunkten
+    assert_eq!(
+        crowding_distances(&objectives, &fronts[1]),
+        vec![f64::INFINITY]
+    );
+}
+
+// Maximiert die XOR Fitness und minimiert die Anzahl der aktiven Connections
+fn evaluate(organisms: &mut [Organism]) {
+    for organism in organisms {
+        let score = common::xor_score(organism);
+        organism.fitness = Some(score);
+        organism.objectives = Some(vec![
+            score,
+            organism.genome().enabled_connection_count() as f64,
+        ]);
+    }
+}
+
+#[test]
+fn organisms_are_ranked_by_pareto_fronts() {
+    let dir = common::test_dir("multi_objective");
+    let config = common::write_config_with(&dir, 23, 10, |config| {
+        config["multi_objective"] = serde_json::json!({ "objectives": ["Maximize", "Minimize"] });
+    });
+    let target = dir.join("population");
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population
+        .write_generations(&target, FileType::Bincode)
+        .unwrap();
+
+    population.next_generation().unwrap();
+    common::xor_fitness(population.organisms_mut());
+    // Ohne Ziele können die Organismen nicht eingeordnet werden
+    assert!(population.finish_generation().is_err());
+
+    for _ in 0..3 {
+        evaluate(population.organisms_mut());
+        population.next_generation().unwrap();
+    }
+    evaluate(population.organisms_mut());
+    population.finish_generation().unwrap();
+
+    // Organismen einer besseren Front werden immer bevorzugt
+    let organisms = population.organisms();
+    assert!(organisms.iter().any(|o| o.pareto_rank() == Some(0)));
+    for a in organisms {
+        for b in organisms {
+            if a.pareto_rank().unwrap() < b.pareto_rank().unwrap() {
+                assert!(a.selection_fitness().unwrap() > b.selection_fitness().unwrap());
+            }
+        }
+    }
+
+    // Die Fronten werden beim Fortsetzen aus den gespeicherten Zielen berechnet
+    let resumed = Population::resume(&config, &target).unwrap();
+    assert_eq!(resumed.organisms().len(), population.organisms().len());
+    // Auch die Organismen der Spezies, aus denen die Eltern gewählt werden
+    let species_organisms = |population: &Population| -> Vec<Organism> {
+        population
+            .species()
+            .iter()
+            .flat_map(|s| s.organisms().iter().map(|o| (**o).clone()))
+            .collect()
+    };
+    let (resumed_species, species) = (species_organisms(&resumed), species_organisms(&population));
+    for (resumed, organism) in resumed
+        .organisms()
+        .iter()
+        .zip(population.organisms())
+        .chain(resumed_species.iter().zip(&species))
+    {
+        assert_eq!(resumed.objectives, organism.objectives);
+        assert_eq!(resumed.pareto_rank(), organism.pareto_rank());
+        assert_eq!(resumed.selection_fitness(), organism.selection_fitness());
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn novelty_and_multi_objective_are_exclusive() {
+    let dir = common::test_dir("multi_objective_novelty");
+    let config = common::write_config_with(&dir, 23, 10, |config| {
+        config["multi_objective"] = serde_json::json!({ "objectives": ["Maximize"] });
+        config["novelty"] = serde_json::json!({
+            "nearest_neighbours": 10,
+            "fitness_weight": 0.0,
+            "archive_insertion": { "MostNovel": { "count": 5 } },
+            "max_archive_size": 0
+        });
+    });
+    assert!(Population::new(GenePool::new_dense(2, 1), &config).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use std::fs;
+
+use rusty_neat_core::{
+    config_util::NormalDistribution,
+    gene_pool::GenePool,
+    genome::{MutationConfig, NewConnectionWeight},
+    population::Population,
+    reproduction::AnnealingConfig,
+};
+
+// Mutiert nichts außer dem Hinzufügen von Nodes mit der Wahrscheinlichkeit add_node_prob
+fn mutation(add_node_prob: f64) -> serde_json::Value {
+    let dist = serde_json::json!({ "mean": 0.0, "std_dev": 1.0 });
+    serde_json::json!({
+        "change_weight_prob": 0.0,
+        "random_weight_dist": dist,
+        "shift_weight_prob": 0.0,
+        "shift_weight_dist": dist,
+        "add_node_prob": add_node_prob,
+        "add_connection_prob": 0.0,
+        "add_connection_retry_count": 0,
+        "new_connection_weight": { "Fixed": 1.0 },
+        "toggle_connection_prob": 0.0,
+        "delete_connection_prob": 0.0,
+        "delete_node_prob": 0.0,
+        "change_activation_prob": 0.0,
+        "activation_options": [],
+        "change_bias_prob": 0.0,
+        "random_bias_dist": dist,
+        "shift_bias_prob": 0.0,
+        "shift_bias_dist": dist
+    })
+}
+
+// Die Stufe mit condition fügt in jedem Nachkommen eine Node hinzu, alle übrigen Mutationen sind leer.
+// Gibt für jede Generation zurück, ob ein Nachkomme eine neue Node erhalten hat.
+fn grown_per_generation(name: &str, condition: serde_json::Value, generations: u32) -> Vec<bool> {
+    let dir = common::test_dir(name);
+    let config = common::write_config_with(&dir, 43, 0, |config| {
+        // Alle Organismen bilden eine einzige, nie stagnierende Spezies
+        config["species"]["species_distance_tolerance"] = 1000.0.into();
+        config["species"]["stagnation_limit"] = 0.into();
+        config["reproduction"]["mutation_tiers"] = serde_json::json!([
+            { "condition": condition, "config": mutation(1.0) },
+            { "condition": "Always", "config": mutation(0.0) }
+        ]);
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+
+    let mut grown = Vec::new();
+    for _ in 0..generations {
+        // Alle Organismen haben die gleiche Fitness, die Spezies verbessert sich also nach Generation 0 nicht mehr
+        population
+            .organisms_mut()
+            .iter_mut()
+            .for_each(|o| o.fitness = Some(1.0));
+        population.next_generation().unwrap();
+        grown.push(
+            population
+                .organisms()
+                .iter()
+                .any(|o| o.genome().node_count() > 3),
+        );
+    }
+    fs::remove_dir_all(&dir).unwrap();
+    grown
+}
+
+#[test]
+fn size_tier_uses_the_actual_species_size() {
+    // Die einzige Spezies enthält alle 150 Organismen
+    assert_eq!(
+        grown_per_generation(
+            "tiers_size_large",
+            serde_json::json!({ "MinSpeciesSize": 150 }),
+            2
+        ),
+        vec![true, true]
+    );
+    assert_eq!(
+        grown_per_generation(
+            "tiers_size_small",
+            serde_json::json!({ "MinSpeciesSize": 151 }),
+            2
+        ),
+        vec![false, false]
+    );
+}
+
+#[test]
+fn age_tier_applies_to_old_species() {
+    // Die Nachkommen der Generation g stammen von der Spezies im Alter g - 1 ab
+    assert_eq!(
+        grown_per_generation("tiers_age", serde_json::json!({ "MinSpeciesAge": 2 }), 4),
+        vec![false, false, true, true]
+    );
+}
+
+#[test]
+fn stagnation_tier_applies_to_stagnant_species() {
+    assert_eq!(
+        grown_per_generation(
+            "tiers_stagnation",
+            serde_json::json!({ "MinStagnation": 3 }),
+            5
+        ),
+        vec![false, false, false, true, true]
+    );
+}
+
+#[test]
+fn last_tier_must_always_apply() {
+    let dir = common::test_dir("tiers_invalid");
+    let config = common::write_config_with(&dir, 43, 10, |config| {
+        config["reproduction"]["mutation_tiers"] = serde_json::json!([
+            { "condition": { "MinSpeciesSize": 10 }, "config": mutation(1.0) }
+        ]);
+    });
+    assert!(Population::new(GenePool::new_dense(2, 1), &config).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn annealing_shrinks_shifts() {
+    let annealing = AnnealingConfig {
+        decay: 0.5,
+        min_factor: 0.2,
+    };
+    assert_eq!(annealing.factor(0), 1.0);
+    assert_eq!(annealing.factor(1), 0.5);
+    assert_eq!(annealing.factor(10), 0.2);
+
+    let config: MutationConfig = serde_json::from_value(mutation(0.0)).unwrap();
+    let annealed = config.annealed(annealing.factor(2));
+    let std_dev = |dist: &NormalDistribution| dist.std_dev;
+    assert_eq!(std_dev(&annealed.shift_weight_dist), 0.25);
+    assert_eq!(std_dev(&annealed.shift_bias_dist), 0.25);
+    assert_eq!(std_dev(&annealed.random_weight_dist), 1.0);
+    assert!(matches!(
+        annealed.new_connection_weight,
+        NewConnectionWeight::Fixed(weight) if weight == 1.0
+    ));
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use std::fs;
+
+use rusty_neat_core::{gene_pool::GenePool, genome::NetworkType, population::Population};
+use rusty_neat_interchange::genome::PrintableGenome;
+
+const INPUTS: [[f64; 2]; 5] = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0], [-0.5, 2.0]];
+
+// Eine Population nach einigen Generationen mit vielen strukturellen Mutationen (und bei rekurrenten Netzwerken
+// vielen Rückwärtskanten)
+fn evolved_population(name: &str, network_type: serde_json::Value) -> Population {
+    let dir = common::test_dir(name);
+    let config = common::write_config_with(&dir, 19, 10, |config| {
+        config["evaluation"]["network_type"] = network_type;
+        for tier in config["reproduction"]["mutation_tiers"]
+            .as_array_mut()
+            .unwrap()
+        {
+            tier["config"]["add_node_prob"] = 0.3.into();
+            tier["config"]["add_connection_prob"] = 0.5.into();
+        }
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+    for _ in 0..5 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+    }
+    fs::remove_dir_all(&dir).unwrap();
+    population
+}
+
+fn has_back_edges(population: &Population) -> bool {
+    population.organisms().iter().any(|o| {
+        PrintableGenome::from(o.genome())
+            .connections
+            .iter()
+            .any(|c| c.enabled && population.pool().connections[c.innovation as usize].recurrent)
+    })
+}
+
+fn assert_close(a: &[f64], b: &[f64]) {
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b) {
+        assert!((a - b).abs() < 1e-12, "{} != {}", a, b);
+    }
+}
+
+// Das kompilierte Netzwerk liefert die gleichen Outputs wie die Auswertung des Genomes, bei rekurrenten
+// Netzwerken auch über eine Folge von Eingaben
+fn assert_network_matches_genome(population: &Population) {
+    let config = &population.config().evaluation;
+    for organism in population.organisms() {
+        let mut genome = organism.genome().clone();
+        let network = organism.genome().compile(config);
+        let mut state = network.new_state();
+        let mut output = [0.0];
+        for input in &INPUTS {
+            network
+                .evaluate_with_state(&mut state, input, &mut output)
+                .unwrap();
+            assert_close(&output, &genome.evaluate(input, config).unwrap());
+        }
+    }
+}
+
+#[test]
+fn network_matches_genome_evaluation() {
+    let population = evolved_population("network_feed_forward", "FeedForward".into());
+    assert!(population
+        .organisms()
+        .iter()
+        .any(|o| o.genome().node_count() > 3));
+    assert_network_matches_genome(&population);
+
+    let population = evolved_population("network_timeline", "Timeline".into());
+    assert!(has_back_edges(&population));
+    assert_network_matches_genome(&population);
+
+    let stabilizing = serde_json::json!({"Stabilizing": {"max_iterations": 20, "tolerance": 0.0}});
+    let population = evolved_population("network_stabilizing", stabilizing);
+    assert_network_matches_genome(&population);
+}
+
+#[test]
+fn batch_evaluation_matches_single_evaluations() {
+    for (name, network_type) in [
+        ("network_batch_feed_forward", NetworkType::FeedForward),
+        ("network_batch_timeline", NetworkType::Timeline),
+    ] {
+        let population = evolved_population(name, serde_json::to_value(network_type).unwrap());
+        let config = &population.config().evaluation;
+        let inputs: Vec<f64> = INPUTS.iter().flatten().copied().collect();
+        for organism in population.organisms() {
+            let network = organism.genome().compile(config);
+            let mut outputs = [0.0; INPUTS.len()];
+            network.evaluate_batch(&inputs, &mut outputs).unwrap();
+
+            // Rekurrente Netzwerke behalten ihren Zustand zwischen den Eingaben eines Batches
+            let mut state = network.new_state();
+            for (input, batch_output) in INPUTS.iter().zip(&outputs) {
+                let mut output = [0.0];
+                network
+                    .evaluate_with_state(&mut state, input, &mut output)
+                    .unwrap();
+                assert_eq!(output[0], *batch_output);
+                if network_type == NetworkType::FeedForward {
+                    network.evaluate(input, &mut output).unwrap();
+                    assert_eq!(output[0], *batch_output);
+                }
+            }
+
+            // Jeder Batch beginnt mit einem frischen Zustand
+            let mut again = [0.0; INPUTS.len()];
+            network.evaluate_batch(&inputs, &mut again).unwrap();
+            assert_eq!(again, outputs);
+        }
+    }
+}
+
+#[test]
+fn organism_evaluation_keeps_the_timeline_state() {
+    let population = evolved_population("network_organism", "Timeline".into());
+    assert!(has_back_edges(&population));
+    for organism in population.organisms() {
+        let network = organism.network();
+        let mut state = network.new_state();
+        let copy = organism.clone();
+        for input in &INPUTS {
+            // evaluate_into schreibt die gleichen Outputs wie evaluate und evaluate_with_state
+            let mut expected = [0.0];
+            network
+                .evaluate_with_state(&mut state, input, &mut expected)
+                .unwrap();
+            let mut output = [0.0];
+            organism.evaluate_into(input, &mut output).unwrap();
+            assert_eq!(output, expected);
+            assert_eq!(copy.evaluate(input).unwrap(), expected);
+
+            // Network::evaluate beginnt immer mit einem frischen Zustand
+            let mut fresh = [0.0];
+            network
+                .evaluate_with_state(&mut network.new_state(), input, &mut fresh)
+                .unwrap();
+            network.evaluate(input, &mut output).unwrap();
+            assert_eq!(output, fresh);
+        }
+        assert!(organism.evaluate_into(&INPUTS[0], &mut [0.0; 2]).is_err());
+    }
+}
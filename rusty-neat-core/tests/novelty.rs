@@ -0,0 +1,97 @@
+mod common;
+
+use std::fs;
+
+use rusty_neat_core::{gene_pool::GenePool, organism::Organism, population::Population};
+use rusty_neat_interchange::io::FileType;
+
+// Das Verhalten eines Organismus sind seine Ausgaben für die vier XOR Eingaben
+fn xor_behaviour(organism: &Organism) -> Vec<f64> {
+    [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]
+        .iter()
+        .map(|input| organism.evaluate(input).unwrap()[0])
+        .collect()
+}
+
+fn evaluate(organisms: &mut [Organism]) {
+    for organism in organisms {
+        organism.fitness = Some(common::xor_score(organism));
+        organism.behaviour = Some(xor_behaviour(organism));
+    }
+}
+
+fn novelty_config(dir: &std::path::Path) -> std::path::PathBuf {
+    common::write_config_with(dir, 17, 10, |config| {
+        config["novelty"] = serde_json::json!({
+            "nearest_neighbours": 10,
+            "fitness_weight": 0.0,
+            "archive_insertion": { "MostNovel": { "count": 5 } },
+            "max_archive_size": 12
+        });
+    })
+}
+
+#[test]
+fn novelty_is_computed_and_archive_grows() {
+    let dir = common::test_dir("novelty");
+    let config = novelty_config(&dir);
+    let target = dir.join("population");
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population
+        .write_generations(&target, FileType::Bincode)
+        .unwrap();
+
+    population.next_generation().unwrap();
+    common::xor_fitness(population.organisms_mut());
+    // Ohne Verhalten kann die Novelty nicht berechnet werden
+    assert!(population.finish_generation().is_err());
+
+    evaluate(population.organisms_mut());
+    population.finish_generation().unwrap();
+    assert_eq!(population.archive().len(), 5);
+    assert!(population
+        .organisms()
+        .iter()
+        .all(|o| o.novelty().is_some() && o.selection_fitness() == o.novelty()));
+    assert!(target.join("archive-0.bin").exists());
+
+    population.next_generation().unwrap();
+    evaluate(population.organisms_mut());
+    population.finish_generation().unwrap();
+    // Die ältesten Einträge werden entfernt
+    assert_eq!(population.archive().len(), 10);
+
+    population.next_generation().unwrap();
+    evaluate(population.organisms_mut());
+    population.finish_generation().unwrap();
+    assert_eq!(population.archive().len(), 12);
+
+    let resumed = Population::resume(&config, &target).unwrap();
+    assert_eq!(
+        resumed.archive().behaviours(),
+        population.archive().behaviours()
+    );
+    assert!(resumed
+        .species()
+        .iter()
+        .flat_map(|s| s.organisms())
+        .all(|o| o.selection_fitness() == o.novelty()));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn invalid_novelty_config_is_rejected() {
+    let dir = common::test_dir("novelty_invalid");
+    let config = common::write_config_with(&dir, 17, 10, |config| {
+        config["novelty"] = serde_json::json!({
+            "nearest_neighbours": 0,
+            "fitness_weight": 0.5,
+            "archive_insertion": { "Threshold": { "threshold": 1.0 } },
+            "max_archive_size": 0
+        });
+    });
+    assert!(Population::new(GenePool::new_dense(2, 1), &config).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use std::{
+    fs,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use rusty_neat_core::{
+    gene_pool::GenePool,
+    observer::{CsvObserver, EvolutionControl, EvolutionObserver, GenerationStatistics},
+    organism::Organism,
+    population::Population,
+};
+use rusty_neat_interchange::io::FileType;
+
+#[derive(Default)]
+struct Events {
+    started: Vec<u32>,
+    ended: Vec<u32>,
+    best: Vec<u32>,
+    created: Vec<usize>,
+    extinct: Vec<usize>,
+}
+
+// Zeichnet alle Ereignisse auf und beendet die Evolution nach stop_after Generationen
+struct RecordingObserver {
+    events: Arc<Mutex<Events>>,
+    stop_after: u32,
+}
+
+impl EvolutionObserver for RecordingObserver {
+    fn on_generation_start(&mut self, generation: u32, _: usize, _: usize) {
+        self.events.lock().unwrap().started.push(generation);
+    }
+
+    fn on_generation_end(&mut self, statistics: &GenerationStatistics) -> EvolutionControl {
+        assert!(statistics.best_fitness >= statistics.mean_fitness);
+        self.events
+            .lock()
+            .unwrap()
+            .ended
+            .push(statistics.generation);
+        if statistics.generation >= self.stop_after {
+            EvolutionControl::Stop
+        } else {
+            EvolutionControl::Continue
+        }
+    }
+
+    fn on_best_organism(&mut self, generation: u32, organism: &Organism) {
+        assert!(organism.fitness.is_some());
+        self.events.lock().unwrap().best.push(generation);
+    }
+
+    fn on_species_created(&mut self, _: u32, species_id: usize) {
+        self.events.lock().unwrap().created.push(species_id);
+    }
+
+    fn on_species_extinct(&mut self, _: u32, species_id: usize) {
+        self.events.lock().unwrap().extinct.push(species_id);
+    }
+}
+
+#[test]
+fn observers_receive_events_and_can_stop_evolution() {
+    let dir = common::test_dir("observer");
+    let config = common::write_config(&dir, 7, 50);
+    let events = Arc::new(Mutex::new(Events::default()));
+
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.add_observer(RecordingObserver {
+        events: Arc::clone(&events),
+        stop_after: 4,
+    });
+    population.add_observer(CsvObserver::create(&dir.join("statistics.csv")).unwrap());
+    population
+        .write_generations(&dir.join("population"), FileType::Bincode)
+        .unwrap();
+    population.evolve(common::xor_fitness).unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.started, vec![0, 1, 2, 3, 4]);
+    assert_eq!(events.ended, vec![0, 1, 2, 3, 4]);
+    assert_eq!(events.best, vec![0, 1, 2, 3, 4]);
+    assert!(!events.created.is_empty());
+    for (i, id) in events.created.iter().enumerate() {
+        assert_eq!(*id, i);
+    }
+    assert!(events.extinct.iter().all(|id| events.created.contains(id)));
+
+    let csv = fs::read_to_string(dir.join("statistics.csv")).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with("generation,"));
+    assert!(lines[5].starts_with("4,"));
+    assert!(dir.join("population").join("gen-4.bin").exists());
+    assert!(!dir.join("population").join("gen-5.bin").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// Ein Writer, dessen Ziel voll ist
+struct FullWriter;
+
+impl Write for FullWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("no space left"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn csv_write_errors_stop_evolution_and_are_kept() {
+    let dir = common::test_dir("observer_csv_error");
+    let config = common::write_config(&dir, 7, 50);
+
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    let observer = CsvObserver::new(FullWriter);
+    let error = observer.error();
+    population.add_observer(observer);
+    population.evolve(common::xor_fitness).unwrap();
+
+    assert_eq!(population.generation(), 0);
+    assert_eq!(
+        error.lock().unwrap().as_ref().unwrap().to_string(),
+        "no space left"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
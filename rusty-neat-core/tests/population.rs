@@ -0,0 +1,171 @@
+mod common;
+
+use std::{
+    fs,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use rusty_neat_core::{
+    gene_pool::GenePool,
+    observer::{EvolutionControl, EvolutionObserver},
+    organism::Organism,
+    population::Population,
+};
+use rusty_neat_interchange::{
+    generation::{self, PrintableGeneration},
+    io::FileType,
+};
+
+#[test]
+fn step_wise_evolution() {
+    let dir = common::test_dir("step_wise");
+    let config = common::write_config(&dir, 3, 10);
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+
+    assert!(population.organisms().is_empty());
+    assert!(population.best().is_none());
+
+    // Generation 0 erzeugen
+    assert_eq!(
+        population.next_generation().unwrap(),
+        EvolutionControl::Continue
+    );
+    assert_eq!(population.generation(), 0);
+    assert_eq!(population.organisms().len(), 150);
+    assert!(population.species().is_empty());
+
+    // Unbewertete Organismen können nicht abgeschlossen werden
+    assert!(population.next_generation().is_err());
+
+    for generation in 1..=5 {
+        common::xor_fitness(population.organisms_mut());
+        let best_fitness = population.best().unwrap().fitness.unwrap();
+        assert!(population
+            .organisms()
+            .iter()
+            .all(|o| o.fitness.unwrap() <= best_fitness));
+
+        population.next_generation().unwrap();
+        assert_eq!(population.generation(), generation);
+        assert!(!population.organisms().is_empty());
+        assert!(!population.species().is_empty());
+        assert_eq!(
+            population
+                .species()
+                .iter()
+                .map(|s| s.organisms().len())
+                .sum::<usize>(),
+            150
+        );
+    }
+
+    // Ohne write_generations wird nichts geschrieben
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn finish_generation_writes_only_when_requested() {
+    let dir = common::test_dir("finish_generation");
+    let config = common::write_config(&dir, 5, 10);
+    let target = dir.join("population");
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population
+        .write_generations(&target, FileType::CompactJSON)
+        .unwrap();
+
+    population.next_generation().unwrap();
+    common::xor_fitness(population.organisms_mut());
+    population.finish_generation().unwrap();
+    assert!(population.finish_generation().is_err());
+    assert!(target.join("gen-0.cjson").exists());
+
+    let resumed = Population::resume(&config, &target).unwrap();
+    assert_eq!(resumed.generation(), 0);
+    assert_eq!(resumed.organisms().len(), 150);
+    assert_eq!(resumed.species().len(), population.species().len());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+struct StagnationCounter(Arc<AtomicUsize>);
+
+impl EvolutionObserver for StagnationCounter {
+    fn on_species_stagnated(&mut self, _: u32, _: usize, stagnant_generations: u32) {
+        assert!(stagnant_generations >= 2);
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn stagnant_species_are_removed() {
+    let dir = common::test_dir("stagnation");
+    let config = common::write_config_with(&dir, 11, 10, |config| {
+        config["species"]["stagnation_limit"] = 2.into();
+        config["species"]["protected_species_count"] = 1.into();
+        config["species"]["species_distance_tolerance"] = 0.5.into();
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+
+    // Ohne Verbesserung der Fitness stagnieren alle Spezies
+    let constant_fitness =
+        |organisms: &mut [Organism]| organisms.iter_mut().for_each(|o| o.fitness = Some(1.0));
+
+    population.next_generation().unwrap();
+    let stagnated = Arc::new(AtomicUsize::new(0));
+    population.add_observer(StagnationCounter(Arc::clone(&stagnated)));
+    for _ in 0..8 {
+        constant_fitness(population.organisms_mut());
+        population.finish_generation().unwrap();
+
+        let generation = population.generation();
+        let stagnant = population
+            .species()
+            .iter()
+            .filter(|s| s.stagnant_generations(generation) >= 2)
+            .count();
+        assert!(stagnant <= 1);
+
+        population.next_generation().unwrap();
+    }
+    assert!(!population.species().is_empty());
+    assert!(stagnated.load(Ordering::SeqCst) > 0);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dynamic_tolerance_approaches_target_species_count() {
+    let dir = common::test_dir("dynamic_tolerance");
+    let config = common::write_config_with(&dir, 13, 12, |config| {
+        config["species"]["species_distance_tolerance"] = 0.5.into();
+        config["species"]["dynamic_tolerance"] = serde_json::json!({
+            "target_species_count": 3,
+            "step": 0.5,
+            "min_tolerance": 0.5,
+            "max_tolerance": 20.0
+        });
+    });
+    let target = dir.join("population");
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population
+        .write_generations(&target, FileType::Bincode)
+        .unwrap();
+    population.evolve(common::xor_fitness).unwrap();
+
+    // Mit einer kleinen Toleranz entstehen zu viele Spezies, also muss die Toleranz wachsen
+    assert!(population.distance_tolerance() > 0.5);
+    assert!(population.distance_tolerance() <= 20.0);
+    assert!(population.species().len() < 20);
+
+    let last: PrintableGeneration = generation::read(&target.join("gen-12.bin")).unwrap();
+    assert_eq!(
+        last.distance_tolerance,
+        Some(population.distance_tolerance())
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
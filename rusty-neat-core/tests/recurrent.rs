@@ -0,0 +1,93 @@
+use rand::{rngs::StdRng, SeedableRng};
+use rusty_neat_core::{
+    activation::Activation,
+    gene_pool::GenePool,
+    genome::{EvaluationConfig, Genome, NetworkType, NewConnectionWeight},
+};
+
+// Input -> Hidden -> Output mit der Rückwärtskante Output -> Hidden (Gewicht 0.5), alle Nodes ohne Bias und
+// mit der Identität als Aktivierungsfunktion. Der Output ist also input + 0.5 * vorheriger Output.
+fn recurrent_genome() -> Genome {
+    let mut pool = GenePool::new();
+    pool.allow_recurrent = true;
+    let input = pool.create_input_node(0.5);
+    let output = pool.create_output_node(0.5);
+    let hidden = pool.create_hidden_node(0.5, 0.5);
+
+    let mut genome = Genome::new(0, 0, 1, 1, Activation::Identity, 0.0);
+    genome.add_node(hidden, Activation::Identity, 0.0);
+    let mut rng = StdRng::seed_from_u64(0);
+    for (from, to, weight) in [
+        (input, hidden, 1.0),
+        (hidden, output, 1.0),
+        (output, hidden, 0.5),
+    ] {
+        let connection = pool.create_connection(from, to).unwrap();
+        assert_eq!(connection.recurrent, from == output);
+        genome.add_new_connection(connection, &NewConnectionWeight::Fixed(weight), &mut rng);
+    }
+    genome
+}
+
+fn config(network_type: NetworkType) -> EvaluationConfig {
+    EvaluationConfig {
+        network_type,
+        ..EvaluationConfig::default()
+    }
+}
+
+#[test]
+fn timeline_keeps_the_state_between_evaluations() {
+    let mut genome = recurrent_genome();
+    let config = config(NetworkType::Timeline);
+
+    // Die Rückwärtskante liest den Output der vorherigen Auswertung
+    assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [1.0]);
+    assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [1.5]);
+    assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [1.75]);
+    assert_eq!(genome.evaluate(&[0.0], &config).unwrap(), [0.875]);
+
+    genome.reset_state();
+    assert_eq!(genome.evaluate(&[2.0], &config).unwrap(), [2.0]);
+    assert_eq!(genome.evaluate(&[2.0], &config).unwrap(), [3.0]);
+}
+
+#[test]
+fn stabilizing_iterates_until_the_values_converge() {
+    let mut genome = recurrent_genome();
+    let stabilizing = config(NetworkType::Stabilizing {
+        max_iterations: 100,
+        tolerance: 1e-9,
+    });
+
+    // Der Fixpunkt von x = 1 + 0.5 * x ist 2, unabhängig von vorherigen Auswertungen
+    for _ in 0..3 {
+        let output = genome.evaluate(&[1.0], &stabilizing).unwrap()[0];
+        assert!((output - 2.0).abs() < 1e-8);
+    }
+
+    // Im ersten Durchlauf liefern Rückwärtskanten 0, jeder weitere Durchlauf liest den vorherigen
+    for (max_iterations, expected) in [(1, 1.0), (2, 1.5), (3, 1.75)] {
+        let config = config(NetworkType::Stabilizing {
+            max_iterations,
+            tolerance: 0.0,
+        });
+        assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [expected]);
+    }
+
+    // Der Zustand einer Timeline-Auswertung beeinflusst Stabilizing nicht
+    genome
+        .evaluate(&[5.0], &config(NetworkType::Timeline))
+        .unwrap();
+    let output = genome.evaluate(&[1.0], &stabilizing).unwrap()[0];
+    assert!((output - 2.0).abs() < 1e-8);
+}
+
+#[test]
+fn feed_forward_evaluation_ignores_back_edges() {
+    let mut genome = recurrent_genome();
+    let config = config(NetworkType::FeedForward);
+    for _ in 0..3 {
+        assert_eq!(genome.evaluate(&[1.0], &config).unwrap(), [1.0]);
+    }
+}
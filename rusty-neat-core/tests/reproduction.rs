@@ -0,0 +1,143 @@
+mod common;
+
+use std::fs;
+
+use proptest::prelude::*;
+use rusty_neat_core::{
+    error::NeatError, gene_pool::GenePool, population::Population, reproduction,
+};
+
+// Anteile der Spezies, auch negative und gleiche Werte sowie Spezies ohne Anteil
+fn shares() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec(
+        prop_oneof![Just(0.0), Just(1.0), -10.0..0.0, 0.0..100.0],
+        1..40,
+    )
+}
+
+proptest! {
+    #[test]
+    fn allocation_is_exact(shares in shares(), total in 0..2000usize, min_size in 0..50usize) {
+        let counts = reproduction::allocate_offspring(&shares, total, min_size);
+        prop_assert_eq!(counts.len(), shares.len());
+        prop_assert_eq!(counts.iter().sum::<usize>(), total);
+
+        // Reicht total nicht für alle Spezies, sinkt die Mindestgröße
+        let min_size = min_size.min(total / shares.len());
+        prop_assert!(counts.iter().all(|count| *count >= min_size));
+    }
+
+    #[test]
+    fn allocation_follows_the_shares(shares in shares(), total in 0..2000usize, min_size in 0..50usize) {
+        let counts = reproduction::allocate_offspring(&shares, total, min_size);
+        for i in 0..shares.len() {
+            for j in 0..shares.len() {
+                if shares[i].max(0.0) > shares[j].max(0.0) {
+                    prop_assert!(counts[i] >= counts[j], "{:?} -> {:?}", shares, counts);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn allocation_without_minimum_is_proportional(shares in shares(), total in 0..2000usize) {
+        let counts = reproduction::allocate_offspring(&shares, total, 0);
+        let positive: Vec<f64> = shares.iter().map(|share| share.max(0.0)).collect();
+        let sum: f64 = positive.iter().sum();
+        for (share, count) in positive.iter().zip(&counts) {
+            let quota = if sum > 0.0 {
+                share / sum * total as f64
+            } else {
+                total as f64 / shares.len() as f64
+            };
+            prop_assert!((*count as f64 - quota).abs() < 1.0, "{:?} -> {:?}", shares, counts);
+        }
+    }
+}
+
+#[test]
+fn species_below_the_minimum_are_raised() {
+    // Die zweite und dritte Spezies erhalten min_size, der Rest wird proportional verteilt
+    assert_eq!(
+        reproduction::allocate_offspring(&[10.0, 1.0, 0.0, 5.0], 100, 10),
+        [53, 10, 10, 27]
+    );
+    // Zu viele Spezies für die Mindestgröße
+    assert_eq!(
+        reproduction::allocate_offspring(&[1.0, 1.0, 1.0], 7, 5),
+        [3, 2, 2]
+    );
+    assert!(reproduction::allocate_offspring(&[], 10, 2).is_empty());
+}
+
+#[test]
+fn generations_have_exactly_organism_count_organisms() {
+    let dir = common::test_dir("reproduction_exact");
+    // Viele kleine Spezies mit Elitismus, bei denen die Rundung bisher zu Abweichungen führte
+    let config = common::write_config_with(&dir, 11, 10, |config| {
+        config["species"]["species_distance_tolerance"] = 0.5.into();
+        config["reproduction"]["min_species_size"] = 3.into();
+        config["reproduction"]["elitism_limit"] = 2.into();
+        config["reproduction"]["elitism_count"] = 2.into();
+        config["reproduction"]["mutation_ratio"] = 0.3.into();
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+
+    population.next_generation().unwrap();
+    for _ in 0..10 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+        assert_eq!(population.organisms().len(), 150);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// Bewertungen aller Organismen nach einigen Generationen mit vielen kleinen Spezies
+fn scores_with_mating(dir_name: &str, mating: serde_json::Value) -> Vec<f64> {
+    let dir = common::test_dir(dir_name);
+    let config = common::write_config_with(&dir, 5, 10, |config| {
+        config["species"]["species_distance_tolerance"] = 0.5.into();
+        config["reproduction"]["min_species_size"] = 3.into();
+        config["reproduction"]["interspecies_mating"] = mating;
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+    for _ in 0..4 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+        assert_eq!(population.organisms().len(), 150);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+    population
+        .organisms()
+        .iter()
+        .map(common::xor_score)
+        .collect()
+}
+
+#[test]
+fn interspecies_mating() {
+    let without = scores_with_mating("reproduction_mating_none", serde_json::Value::Null);
+    for partner in ["Random", "Nearest"] {
+        let mating = serde_json::json!({"rate": 0.5, "partner": partner});
+        let dir_name = format!("reproduction_mating_{}", partner);
+        let scores = scores_with_mating(&dir_name, mating.clone());
+        assert_ne!(scores, without);
+        // Auch die Kreuzung zwischen Spezies ist mit einem Seed reproduzierbar
+        assert_eq!(scores, scores_with_mating(&dir_name, mating));
+    }
+
+    let dir = common::test_dir("reproduction_mating_invalid");
+    let config = common::write_config_with(&dir, 5, 10, |config| {
+        config["reproduction"]["interspecies_mating"] =
+            serde_json::json!({"rate": 1.5, "partner": "Random"});
+    });
+    match Population::new(GenePool::new_dense(2, 1), &config) {
+        Err(NeatError::InvalidConfig(issues)) => {
+            assert_eq!(issues[0].field, "reproduction.interspecies_mating.rate")
+        }
+        _ => panic!("expected an invalid config error"),
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,250 @@
+mod common;
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rusty_neat_core::{
+    activation::Activation, config::ConfigLoader, error::NeatError, gene_pool::GenePool,
+    population::Population,
+};
+use rusty_neat_interchange::{
+    generation::{self, PrintableGeneration},
+    genome::PrintableGenome,
+    io::FileType,
+};
+
+// Viele kleine Spezies, die schnell wegen Stagnation entfernt werden
+fn write_config(dir: &Path, max_generations: u32) -> PathBuf {
+    common::write_config_with(dir, 17, max_generations, |config| {
+        config["species"]["species_distance_tolerance"] = 0.5.into();
+        config["species"]["stagnation_limit"] = 2.into();
+        config["species"]["protected_species_count"] = 1.into();
+        config["reproduction"]["min_species_size"] = 3.into();
+    })
+}
+
+// Bewertet und schließt die ersten generations Generationen ab, als ob die Evolution danach abbricht
+fn interrupted_run(
+    config: &Path,
+    target: &Path,
+    file_type: FileType,
+    generations: u32,
+) -> Population {
+    let mut population = Population::new(GenePool::new_dense(2, 1), config).unwrap();
+    population.write_generations(target, file_type).unwrap();
+    population.next_generation().unwrap();
+    for _ in 0..generations {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+    }
+    common::xor_fitness(population.organisms_mut());
+    population.finish_generation().unwrap();
+    population
+}
+
+#[test]
+fn resumed_evolution_matches_uninterrupted_run() {
+    let dir = common::test_dir("resume_identical");
+    let config = write_config(&dir, 10);
+
+    let uninterrupted = dir.join("uninterrupted");
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population
+        .write_generations(&uninterrupted, FileType::Bincode)
+        .unwrap();
+    population.evolve(common::xor_fitness).unwrap();
+
+    let interrupted = dir.join("interrupted");
+    let population = interrupted_run(&config, &interrupted, FileType::Bincode, 5);
+    let in_species: usize = population
+        .species()
+        .iter()
+        .map(|s| s.organisms().len())
+        .sum();
+    assert!(in_species < population.organisms().len());
+
+    // Auch die Organismen der wegen Stagnation entfernten Spezies werden wiederhergestellt
+    let mut resumed = Population::resume(&config, &interrupted).unwrap();
+    assert_eq!(resumed.generation(), 5);
+    let ids = |population: &Population| -> Vec<u64> {
+        population
+            .organisms()
+            .iter()
+            .map(|o| o.genome().id())
+            .collect()
+    };
+    assert_eq!(ids(&resumed), ids(&population));
+    assert_eq!(
+        resumed.best().unwrap().genome().id(),
+        population.best().unwrap().genome().id()
+    );
+
+    resumed
+        .write_generations(&interrupted, FileType::Bincode)
+        .unwrap();
+    resumed.evolve(common::xor_fitness).unwrap();
+    assert_eq!(resumed.generation(), 10);
+    assert_eq!(
+        common::read_files(&uninterrupted),
+        common::read_files(&interrupted)
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn unversioned_generation_files_can_be_resumed() {
+    let dir = common::test_dir("resume_unversioned");
+    let config = write_config(&dir, 10);
+    let target = dir.join("population");
+    let population = interrupted_run(&config, &target, FileType::PrettyJSON, 3);
+
+    // Während der Entwicklung von Version 1 wurden die Generationen ohne Versionsangabe geschrieben, die
+    // ersten Dateien enthielten nur die Spezies und den Gen-Pool
+    let path = target.join("gen-3.json");
+    let file: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(file["format_version"], generation::FORMAT_VERSION);
+    let mut generation = file["content"].clone();
+    let object = generation.as_object_mut().unwrap();
+    for field in [
+        "organisms",
+        "next_species_id",
+        "last_genome_id",
+        "distance_tolerance",
+        "phase",
+    ] {
+        assert!(object.remove(field).is_some());
+    }
+    fs::write(&path, serde_json::to_string(&generation).unwrap()).unwrap();
+
+    let mut resumed = Population::resume(&config, &target).unwrap();
+    assert_eq!(resumed.generation(), 3);
+    assert_eq!(resumed.distance_tolerance(), 0.5);
+    let in_species: usize = population
+        .species()
+        .iter()
+        .map(|s| s.organisms().len())
+        .sum();
+    assert_eq!(resumed.organisms().len(), in_species);
+
+    // Neue Genomes und Spezies erhalten weiterhin eindeutige Ids
+    let old_ids: HashSet<u64> = population
+        .organisms()
+        .iter()
+        .map(|o| o.genome().id())
+        .collect();
+    let old_species: HashSet<usize> = population.species().iter().map(|s| s.id()).collect();
+    common::xor_fitness(resumed.organisms_mut());
+    resumed.next_generation().unwrap();
+    common::xor_fitness(resumed.organisms_mut());
+    resumed.finish_generation().unwrap();
+    let new_ids: Vec<u64> = resumed
+        .organisms()
+        .iter()
+        .map(|o| o.genome().id())
+        .filter(|id| !old_ids.contains(id))
+        .collect();
+    assert!(!new_ids.is_empty());
+    assert!(new_ids
+        .iter()
+        .all(|id| *id > *old_ids.iter().max().unwrap()));
+    assert!(resumed
+        .species()
+        .iter()
+        .filter(|s| !old_species.contains(&s.id()))
+        .all(|s| s.id() > *old_species.iter().max().unwrap()));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// Die Generationen in connect_four_out wurden vor der Einführung der Formatversion geschrieben (Version 0)
+#[test]
+fn baseline_generation_files_can_be_resumed() {
+    let repository = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let dir = common::test_dir("resume_baseline");
+    let target = dir.join("population");
+    fs::create_dir_all(&target).unwrap();
+    fs::copy(
+        repository.join("connect_four_out/gen-7.bin"),
+        target.join("gen-7.bin"),
+    )
+    .unwrap();
+
+    // Version 0 verwendete für alle Nodes außer den Inputs evaluation.activation und evaluation.bias
+    let config = ConfigLoader::new()
+        .file(&repository.join("connect_four.json"))
+        .and_then(|loader| loader.set("evaluation.activation", "Tanh"))
+        .and_then(|loader| loader.set("evaluation.bias", "0.25"))
+        .and_then(|loader| loader.load())
+        .unwrap();
+    let mut resumed = Population::resume_with_config(config, &target).unwrap();
+    assert_eq!(resumed.generation(), 7);
+    assert_eq!(resumed.organisms().len(), 100);
+    assert!(resumed.organisms().iter().all(|o| o.fitness.is_some()));
+
+    let input_count = resumed.organisms()[0].network().input_count();
+    for organism in resumed.organisms() {
+        let genome = PrintableGenome::from(organism.genome());
+        assert!(genome.nodes.len() > input_count);
+        for (i, node) in genome.nodes.iter().enumerate() {
+            let (activation, bias) = if i < input_count {
+                (Activation::Identity, 0.0)
+            } else {
+                (Activation::Tanh, 0.25)
+            };
+            assert_eq!(Activation::from(&node.activation), activation);
+            assert_eq!(node.bias, bias);
+        }
+    }
+
+    // Die Evolution wird fortgesetzt und schreibt Dateien mit Versionsangabe
+    resumed
+        .write_generations(&target, FileType::Bincode)
+        .unwrap();
+    let input = vec![0.5; input_count];
+    for _ in 0..2 {
+        resumed
+            .organisms_mut()
+            .iter_mut()
+            .for_each(|o| o.fitness = Some(o.evaluate(&input).unwrap().iter().sum::<f64>().abs()));
+        resumed.next_generation().unwrap();
+    }
+    assert_eq!(resumed.generation(), 9);
+    let written: PrintableGeneration = generation::read(&target.join("gen-8.bin")).unwrap();
+    assert_eq!(written.generation, 8);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn generation_files_of_unsupported_versions_are_rejected() {
+    let dir = common::test_dir("resume_unsupported");
+    let config = write_config(&dir, 10);
+    let target = dir.join("population");
+    interrupted_run(&config, &target, FileType::PrettyJSON, 1);
+
+    let path = target.join("gen-1.json");
+    let mut file: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    file["format_version"] = (generation::FORMAT_VERSION + 1).into();
+    fs::write(&path, serde_json::to_string(&file).unwrap()).unwrap();
+    match Population::resume(&config, &target) {
+        Err(NeatError::UnsupportedVersion { version, .. }) => {
+            assert_eq!(version, Some(generation::FORMAT_VERSION + 1))
+        }
+        _ => panic!("expected an unsupported version error"),
+    }
+
+    fs::remove_file(&path).unwrap();
+    fs::write(target.join("gen-1.bin"), [1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+    assert!(matches!(
+        Population::resume(&config, &target),
+        Err(NeatError::UnsupportedVersion { version: None, .. })
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
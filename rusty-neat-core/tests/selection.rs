@@ -0,0 +1,162 @@
+use rand::{rngs::StdRng, SeedableRng};
+use rusty_neat_core::{config::Validate, reproduction::SpeciesReproductionStrategy};
+
+const SAMPLES: usize = 200_000;
+const TOLERANCE: f64 = 0.01;
+
+// Relative Häufigkeit, mit der jeder Index ausgewählt wurde
+fn frequencies(strategy: &SpeciesReproductionStrategy, fitness: &[f64]) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut counts = vec![0; fitness.len()];
+    for _ in 0..SAMPLES {
+        counts[strategy.select(fitness, &mut rng)] += 1;
+    }
+    counts
+        .into_iter()
+        .map(|count| count as f64 / SAMPLES as f64)
+        .collect()
+}
+
+fn assert_distribution(actual: &[f64], expected: &[f64]) {
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (actual - expected).abs() < TOLERANCE,
+            "index {}: expected {:.4}, got {:.4}",
+            i,
+            expected,
+            actual
+        );
+    }
+}
+
+fn mean_index(frequencies: &[f64]) -> f64 {
+    frequencies
+        .iter()
+        .enumerate()
+        .map(|(i, f)| i as f64 * f)
+        .sum()
+}
+
+fn fitness() -> Vec<f64> {
+    (1..=10).map(|f| f as f64).collect()
+}
+
+#[test]
+fn random_selection_is_uniform() {
+    let actual = frequencies(&SpeciesReproductionStrategy::Random, &fitness());
+    assert_distribution(&actual, &[0.1; 10]);
+}
+
+#[test]
+fn roulette_selection_is_proportional_to_fitness() {
+    let fitness = fitness();
+    let total: f64 = fitness.iter().sum();
+    let expected: Vec<f64> = fitness.iter().map(|f| f / total).collect();
+    let actual = frequencies(&SpeciesReproductionStrategy::Roulette, &fitness);
+    assert_distribution(&actual, &expected);
+}
+
+#[test]
+fn roulette_selection_handles_negative_and_zero_fitness() {
+    // Um -2 verschoben ergeben sich die Gewichte 0, 1, 2, 3
+    let actual = frequencies(
+        &SpeciesReproductionStrategy::Roulette,
+        &[-2.0, -1.0, 0.0, 1.0],
+    );
+    assert_distribution(&actual, &[0.0, 1.0 / 6.0, 2.0 / 6.0, 3.0 / 6.0]);
+
+    let actual = frequencies(&SpeciesReproductionStrategy::Roulette, &[0.0; 4]);
+    assert_distribution(&actual, &[0.25; 4]);
+}
+
+#[test]
+fn rank_selection_is_proportional_to_rank() {
+    // Die Fitnesswerte selbst spielen keine Rolle, nur ihre Reihenfolge
+    let fitness = [0.0, 0.001, 0.002, 1000.0];
+    let actual = frequencies(&SpeciesReproductionStrategy::Rank, &fitness);
+    assert_distribution(&actual, &[0.1, 0.2, 0.3, 0.4]);
+}
+
+#[test]
+fn tournament_selection_pressure_grows_with_size() {
+    let fitness = fitness();
+    let n = fitness.len() as f64;
+
+    for size in 1..=4 {
+        // Der Index i gewinnt, wenn das Maximum von size gleichverteilten Indizes i ist
+        let expected: Vec<f64> = (0..fitness.len())
+            .map(|i| ((i + 1) as f64 / n).powi(size) - (i as f64 / n).powi(size))
+            .collect();
+        let actual = frequencies(
+            &SpeciesReproductionStrategy::Tournament {
+                size: size as usize,
+            },
+            &fitness,
+        );
+        assert_distribution(&actual, &expected);
+    }
+
+    let small = frequencies(
+        &SpeciesReproductionStrategy::Tournament { size: 2 },
+        &fitness,
+    );
+    let large = frequencies(
+        &SpeciesReproductionStrategy::Tournament { size: 7 },
+        &fitness,
+    );
+    assert!(mean_index(&large) > mean_index(&small));
+}
+
+#[test]
+fn truncation_selection_only_selects_the_best() {
+    let actual = frequencies(
+        &SpeciesReproductionStrategy::Truncation { ratio: 0.3 },
+        &fitness(),
+    );
+    let mut expected = vec![0.0; 7];
+    expected.extend([1.0 / 3.0; 3].iter());
+    assert_distribution(&actual, &expected);
+
+    // Mindestens ein Organismus wird immer betrachtet
+    let actual = frequencies(
+        &SpeciesReproductionStrategy::Truncation { ratio: 0.01 },
+        &fitness(),
+    );
+    assert_eq!(actual[9], 1.0);
+}
+
+#[test]
+fn selection_pressure_is_ordered() {
+    let fitness = fitness();
+    let random = mean_index(&frequencies(&SpeciesReproductionStrategy::Random, &fitness));
+    let roulette = mean_index(&frequencies(
+        &SpeciesReproductionStrategy::Roulette,
+        &fitness,
+    ));
+    let truncation = mean_index(&frequencies(
+        &SpeciesReproductionStrategy::Truncation { ratio: 0.2 },
+        &fitness,
+    ));
+    assert!(random < roulette);
+    assert!(roulette < truncation);
+}
+
+#[test]
+fn invalid_strategies_are_rejected() {
+    assert!(SpeciesReproductionStrategy::Random.validate().is_ok());
+    assert!(SpeciesReproductionStrategy::Tournament { size: 3 }
+        .validate()
+        .is_ok());
+    assert!(SpeciesReproductionStrategy::Tournament { size: 0 }
+        .validate()
+        .is_err());
+    assert!(SpeciesReproductionStrategy::Truncation { ratio: 1.0 }
+        .validate()
+        .is_ok());
+    assert!(SpeciesReproductionStrategy::Truncation { ratio: 0.0 }
+        .validate()
+        .is_err());
+    assert!(SpeciesReproductionStrategy::Truncation { ratio: 1.5 }
+        .validate()
+        .is_err());
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use std::{
+    fs,
+    sync::{Arc, Mutex},
+};
+
+use rusty_neat_core::{
+    gene_pool::GenePool, observer::EvolutionObserver, population::Population, reproduction::Phase,
+};
+use rusty_neat_interchange::io::FileType;
+
+// Setzt die Wahrscheinlichkeiten für das Hinzufügen und Entfernen in allen Mutationsstufen
+fn set_mutation(
+    config: &mut serde_json::Value,
+    add: f64,
+    delete_connection: f64,
+    delete_node: f64,
+) {
+    for tier in config["reproduction"]["mutation_tiers"]
+        .as_array_mut()
+        .unwrap()
+    {
+        let mutation = &mut tier["config"];
+        mutation["add_node_prob"] = add.into();
+        mutation["add_connection_prob"] = add.into();
+        mutation["delete_connection_prob"] = delete_connection.into();
+        mutation["delete_node_prob"] = delete_node.into();
+    }
+}
+
+#[test]
+fn deletions_shrink_genomes() {
+    let dir = common::test_dir("simplification_delete");
+    let config = common::write_config_with(&dir, 37, 10, |config| {
+        config["initial_topology"]["hidden_nodes"] = 3.into();
+        set_mutation(config, 0.0, 0.5, 0.5);
+    });
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.next_generation().unwrap();
+    assert!(population
+        .organisms()
+        .iter()
+        .all(|o| o.genome().node_count() == 6 && o.genome().connection_count() == 11));
+
+    for _ in 0..3 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+    }
+    // Die verkleinerten Genome lassen sich weiterhin auswerten
+    common::xor_fitness(population.organisms_mut());
+    let organisms = population.organisms();
+    assert!(organisms.iter().any(|o| o.genome().connection_count() < 11));
+    assert!(organisms.iter().any(|o| o.genome().node_count() < 6));
+    assert!(organisms
+        .iter()
+        .all(|o| o.genome().connection_count() <= 11 && o.genome().node_count() >= 3));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+struct PhaseRecorder(Arc<Mutex<Vec<Phase>>>);
+
+impl EvolutionObserver for PhaseRecorder {
+    fn on_phase_changed(&mut self, _generation: u32, phase: Phase) {
+        self.0.lock().unwrap().push(phase);
+    }
+}
+
+#[test]
+fn phases_alternate_between_complexification_and_simplification() {
+    let dir = common::test_dir("simplification_phases");
+    let config = common::write_config_with(&dir, 41, 30, |config| {
+        set_mutation(config, 0.5, 0.0, 0.0);
+        let mut simplification = config["reproduction"]["mutation_tiers"][1]["config"].clone();
+        simplification["add_node_prob"] = 0.0.into();
+        simplification["add_connection_prob"] = 0.0.into();
+        simplification["delete_connection_prob"] = 0.5.into();
+        simplification["delete_node_prob"] = 0.5.into();
+        config["reproduction"]["simplification"] = serde_json::json!({
+            "complexity_threshold": 1.0,
+            "stagnation_limit": 2,
+            "mutation": simplification
+        });
+    });
+    let target = dir.join("population");
+    let phases = Arc::new(Mutex::new(Vec::new()));
+    let mut population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    population.add_observer(PhaseRecorder(Arc::clone(&phases)));
+    population
+        .write_generations(&target, FileType::Bincode)
+        .unwrap();
+    assert_eq!(population.phase(), Phase::Complexifying);
+
+    population.next_generation().unwrap();
+    for _ in 0..20 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+    }
+    common::xor_fitness(population.organisms_mut());
+    population.finish_generation().unwrap();
+
+    let phases = phases.lock().unwrap();
+    assert!(phases.len() >= 2);
+    assert_eq!(phases[0], Phase::Simplifying);
+    assert!(phases.windows(2).all(|pair| pair[0] != pair[1]));
+    assert_eq!(population.phase(), *phases.last().unwrap());
+
+    let resumed = Population::resume(&config, &target).unwrap();
+    assert_eq!(resumed.phase(), population.phase());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
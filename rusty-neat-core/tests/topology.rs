@@ -0,0 +1,88 @@
+mod common;
+
+use std::fs;
+
+use rusty_neat_core::{error::NeatError, gene_pool::GenePool, population::Population};
+
+fn initial_population(
+    name: &str,
+    pool: GenePool,
+    connections: serde_json::Value,
+    hidden_nodes: usize,
+) -> Result<Population, NeatError> {
+    let dir = common::test_dir(name);
+    let config = common::write_config_with(&dir, 31, 10, |config| {
+        config["initial_topology"] = serde_json::json!({
+            "connections": connections,
+            "hidden_nodes": hidden_nodes
+        });
+    });
+    let population = Population::new(pool, &config).and_then(|mut population| {
+        population.next_generation()?;
+        Ok(population)
+    });
+    fs::remove_dir_all(&dir).unwrap();
+    population
+}
+
+#[test]
+fn full_topology_copies_all_pool_connections() {
+    let population =
+        initial_population("topology_full", GenePool::new_dense(2, 1), "Full".into(), 3).unwrap();
+    for organism in population.organisms() {
+        // 2 Inputs, 1 Output und 3 Hidden-Nodes, die mit allen Inputs und Outputs verbunden sind
+        assert_eq!(organism.genome().node_count(), 6);
+        assert_eq!(organism.genome().connection_count(), 2 + 3 * 2 + 3);
+    }
+}
+
+#[test]
+fn unconnected_topology_grows_by_mutation() {
+    let mut population = initial_population(
+        "topology_unconnected",
+        GenePool::new_dense(2, 1),
+        "Unconnected".into(),
+        0,
+    )
+    .unwrap();
+    assert!(population
+        .organisms()
+        .iter()
+        .all(|o| o.genome().connection_count() == 0 && o.genome().node_count() == 3));
+
+    for _ in 0..5 {
+        common::xor_fitness(population.organisms_mut());
+        population.next_generation().unwrap();
+    }
+    assert!(population
+        .organisms()
+        .iter()
+        .any(|o| o.genome().connection_count() > 0));
+}
+
+#[test]
+fn random_topology_selects_a_subset() {
+    let population = initial_population(
+        "topology_random",
+        GenePool::new_dense(10, 4),
+        serde_json::json!({ "Random": { "probability": 0.25 } }),
+        0,
+    )
+    .unwrap();
+    let counts: Vec<usize> = population
+        .organisms()
+        .iter()
+        .map(|o| o.genome().connection_count())
+        .collect();
+    let mean = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+    assert!((5.0..15.0).contains(&mean));
+    assert!(counts.iter().any(|count| *count != counts[0]));
+
+    assert!(initial_population(
+        "topology_invalid",
+        GenePool::new_dense(2, 1),
+        serde_json::json!({ "Random": { "probability": 1.5 } }),
+        0,
+    )
+    .is_err());
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use std::{fs, sync::Arc};
+
+use rusty_neat_core::{
+    config::{ConfigLoader, Validate, Validation},
+    config_util::NormalDistribution,
+    error::NeatError,
+    gene_pool::GenePool,
+    population::{Population, PopulationConfig},
+    reproduction::ReproductionConfig,
+};
+
+fn invalid_fields(config: &PopulationConfig) -> Vec<String> {
+    match config.validate() {
+        Err(NeatError::InvalidConfig(issues)) => {
+            issues.into_iter().map(|issue| issue.field).collect()
+        }
+        Err(err) => panic!("expected an invalid config error, got {}", err),
+        Ok(_) => panic!("expected an invalid config error"),
+    }
+}
+
+#[test]
+fn all_errors_are_reported_at_once() {
+    let dir = common::test_dir("validation_errors");
+    let config = common::write_config_with(&dir, 3, 10, |config| {
+        config["target_fitness"] = (-1.0).into();
+        config["species"]["species_distance_tolerance"] = (-1.0).into();
+        config["reproduction"]["kill_ratio"] = 1.5.into();
+        config["reproduction"]["mutation_ratio"] = 2.0.into();
+        config["reproduction"]["elitism_count"] = 30.into();
+        config["reproduction"]["elitism_limit"] = 20.into();
+        config["reproduction"]["mutation_tiers"][0]["config"]["add_node_prob"] = 1.5.into();
+        config["reproduction"]["mutation_tiers"][1]["config"]["shift_weight_dist"]["std_dev"] =
+            (-0.5).into();
+        config["initial_organism_weight"] = serde_json::json!({
+            "Random": {"mean": 0.0, "std_dev": -1.0}
+        });
+    });
+    let result = Population::new(GenePool::new_dense(2, 1), &config);
+    let fields: Vec<String> = match result {
+        Err(NeatError::InvalidConfig(issues)) => {
+            issues.into_iter().map(|issue| issue.field).collect()
+        }
+        _ => panic!("expected an invalid config error"),
+    };
+    for field in [
+        "target_fitness",
+        "initial_organism_weight.Random.std_dev",
+        "species.species_distance_tolerance",
+        "reproduction.kill_ratio",
+        "reproduction.mutation_ratio",
+        "reproduction.elitism_count",
+        "reproduction.mutation_tiers[0].config.add_node_prob",
+        "reproduction.mutation_tiers[1].config.shift_weight_dist.std_dev",
+    ] {
+        assert!(
+            fields.iter().any(|f| f == field),
+            "{} missing in {:?}",
+            field,
+            fields
+        );
+    }
+    assert_eq!(fields.len(), 8);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn min_species_size_must_fit_the_expected_species() {
+    let dir = common::test_dir("validation_species_size");
+    let config = common::write_config_with(&dir, 3, 10, |config| {
+        config["reproduction"]["min_species_size"] = 10.into();
+        config["species"]["dynamic_tolerance"] = serde_json::json!({
+            "target_species_count": 20,
+            "step": 0.1,
+            "min_tolerance": 0.5,
+            "max_tolerance": 5.0
+        });
+    });
+    // 150 Organismen reichen nicht für 20 Spezies mit je 10 Organismen
+    let loader = ConfigLoader::new().file(&config).unwrap();
+    assert!(matches!(
+        loader.load(),
+        Err(NeatError::InvalidConfig(issues)) if issues[0].field == "reproduction.min_species_size"
+    ));
+
+    let config = PopulationConfig {
+        reproduction: Arc::new(ReproductionConfig {
+            organism_count: 5,
+            ..ReproductionConfig::default()
+        }),
+        ..PopulationConfig::default()
+    };
+    assert_eq!(invalid_fields(&config), ["reproduction.min_species_size"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn normal_distributions_are_checked() {
+    let valid = NormalDistribution {
+        mean: 1.0,
+        std_dev: 0.0,
+    };
+    assert!(valid.validate().unwrap().is_empty());
+    for std_dev in [-1.0, f64::NAN, f64::INFINITY] {
+        let dist = NormalDistribution { mean: 0.0, std_dev };
+        assert!(dist.validate().is_err());
+    }
+
+    let mut validation = Validation::new();
+    validation.section("weights", |validation| {
+        validation.check(
+            "[2]",
+            &NormalDistribution {
+                mean: 0.0,
+                std_dev: -1.0,
+            },
+        );
+    });
+    assert_eq!(validation.errors()[0].field, "weights[2].std_dev");
+}
+
+#[test]
+fn suspicious_settings_are_warnings() {
+    let dir = common::test_dir("validation_warnings");
+    let config = common::write_config(&dir, 3, 10);
+    let population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    assert!(population.config_warnings().is_empty());
+
+    let config = common::write_config_with(&dir, 3, 0, |config| {
+        config["target_fitness"] = f64::MAX.into();
+        config["species"]["species_distance_tolerance"] = 0.0.into();
+        let tiers = &mut config["reproduction"]["mutation_tiers"];
+        // Die erste Stufe mutiert nie, hinter der eingefügten Stufe mit Always liegt die letzte
+        for (key, value) in tiers[0]["config"].as_object_mut().unwrap() {
+            if key.ends_with("_prob") {
+                *value = 0.0.into();
+            }
+        }
+        tiers
+            .as_array_mut()
+            .unwrap()
+            .insert(1, serde_json::json!({"condition": "Always"}));
+    });
+    let population = Population::new(GenePool::new_dense(2, 1), &config).unwrap();
+    let fields: Vec<&str> = population
+        .config_warnings()
+        .iter()
+        .map(|warning| warning.field.as_str())
+        .collect();
+    assert!(fields.contains(&"max_generations"));
+    assert!(fields.contains(&"species.species_distance_tolerance"));
+    assert!(fields.contains(&"reproduction.mutation_tiers[0].config"));
+    assert!(fields.contains(&"reproduction.mutation_tiers[2]"));
+    assert_eq!(fields.len(), 4);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -14,6 +14,9 @@ pub fn generation() -> impl Widget<Arc<Generation>> {
         .with_child(Label::dynamic(|generation: &Arc<Generation>, _| {
             format!("Generation {}", generation.generation)
         }))
+        .with_child(Label::dynamic(|generation: &Arc<Generation>, _| {
+            format!("Distance tolerance {:.3}", generation.distance_tolerance)
+        }))
         .with_default_spacer()
         .with_flex_child(
             Scroll::new(List::new(genome_entry).lens(lens::InArc::new(Generation::species))),
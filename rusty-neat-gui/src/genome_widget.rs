@@ -174,9 +174,16 @@ impl Widget<Arc<Genome>> for GenomeWidget {
                     node.id.to_string() + " (In " + id.to_string().as_str() + ")"
                 }
                 PrintableNodeType::Output(id) => {
-                    node.id.to_string() + " (Out " + id.to_string().as_str() + ")"
+                    node.id.to_string()
+                        + " (Out "
+                        + id.to_string().as_str()
+                        + ", "
+                        + node.activation.as_str()
+                        + ")"
+                }
+                PrintableNodeType::Hidden => {
+                    node.id.to_string() + " (" + node.activation.as_str() + ")"
                 }
-                PrintableNodeType::Hidden => node.id.to_string(),
             };
             let text_layout = ctx
                 .text()
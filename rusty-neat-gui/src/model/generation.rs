@@ -11,6 +11,7 @@ pub struct Generation {
     pub species: Vector<Arc<Species>>,
     pub best_organism: Arc<Organism>,
     pub average_fitness: f64,
+    pub distance_tolerance: f64,
     pub pool: GenePool,
 }
 
@@ -30,6 +31,7 @@ impl From<PrintableGeneration> for Generation {
             species,
             best_organism,
             average_fitness,
+            distance_tolerance: generation.distance_tolerance,
             pool: generation.pool.into(),
         }
     }
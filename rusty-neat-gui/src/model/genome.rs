@@ -27,21 +27,21 @@ impl From<(&PrintableGenome, &PrintableGenePool)> for Genome {
         let mut node_map = HashMap::new();
 
         for node in &printable.nodes {
-            let printable_node = &pool.nodes[*node as usize];
+            let printable_node = &pool.nodes[node.id as usize];
             node_map.insert(
-                node,
+                node.id,
                 Rc::from(Node {
                     id: printable_node.id,
                     position: Point::new(printable_node.depth, printable_node.vertical_placement),
-                    activation: "TODO".to_owned(),
-                    bias: 0.0,
+                    activation: format!("{:?}", node.activation),
+                    bias: node.bias,
                     node_type: Rc::from(printable_node.node_type.clone()),
                 }),
             );
 
             genome
                 .nodes
-                .push_back(Rc::clone(node_map.get(&node).unwrap()));
+                .push_back(Rc::clone(node_map.get(&node.id).unwrap()));
         }
 
         for connection in &printable.connections {
@@ -4,11 +4,12 @@ use std::{fs, path::Path};
 use crate::model::{generation::Generation, neat::NeatModel};
 use im::Vector;
 use rusty_neat_interchange::{
+    error::NeatError,
     generation,
     neat_result::{self, PrintableNeatResult},
 };
 
-pub fn read(path: &Path) -> Result<NeatModel, String> {
+pub fn read(path: &Path) -> Result<NeatModel, NeatError> {
     let mut generations = read_all_generations(path)?;
     let result = read_result(path)?;
     generations.sort_by(|x, y| x.generation.cmp(&y.generation));
@@ -22,11 +23,12 @@ pub fn read(path: &Path) -> Result<NeatModel, String> {
     })
 }
 
-fn read_all_generations(path: &Path) -> Result<Vector<Arc<Generation>>, String> {
+fn read_all_generations(path: &Path) -> Result<Vector<Arc<Generation>>, NeatError> {
     let mut generations = Vector::new();
-    for entry in fs::read_dir(path).map_err(|err| err.to_string())? {
+    let io_error = |err| NeatError::io(path.to_owned(), err);
+    for entry in fs::read_dir(path).map_err(io_error)? {
         println!("Reading");
-        let entry = entry.map_err(|err| err.to_string())?.path();
+        let entry = entry.map_err(io_error)?.path();
         if entry.is_file() && entry.file_name().unwrap().to_str().unwrap().contains("gen") {
             generations.push_back(Arc::new(generation::read(&entry)?));
         }
@@ -35,9 +37,10 @@ fn read_all_generations(path: &Path) -> Result<Vector<Arc<Generation>>, String>
     Ok(generations)
 }
 
-fn read_result(path: &Path) -> Result<Option<PrintableNeatResult>, String> {
-    for entry in fs::read_dir(path).map_err(|err| err.to_string())? {
-        let entry = entry.map_err(|err| err.to_string())?.path();
+fn read_result(path: &Path) -> Result<Option<PrintableNeatResult>, NeatError> {
+    let io_error = |err| NeatError::io(path.to_owned(), err);
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?.path();
         if entry.is_file()
             && entry
                 .file_name()
@@ -0,0 +1,154 @@
+use std::{error::Error, fmt, io, path::PathBuf};
+
+use crate::generation::FORMAT_VERSION;
+
+// Gemeinsamer Fehlertyp von rusty-neat-core und rusty-neat-interchange
+#[derive(Debug)]
+pub enum NeatError {
+    InvalidConfig(Vec<ConfigIssue>), // Alle Fehler der geprüften Konfiguration
+
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    // Der Inhalt einer Datei konnte nicht (de)serialisiert werden
+    Serialization {
+        path: PathBuf,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    UnknownFileExtension(PathBuf),
+    // Eine Generation wurde von einer anderen Version geschrieben und kann nicht gelesen werden
+    // (version ist None, wenn die Datei keine Versionsangabe enthält)
+    UnsupportedVersion {
+        path: PathBuf,
+        version: Option<u32>,
+    },
+    // Ein Netzwerk wurde mit der falschen Anzahl an Inputs bzw. Outputs ausgewertet
+    InputLength {
+        expected: usize,
+        actual: usize,
+    },
+    OutputLength {
+        expected: usize,
+        actual: usize,
+    },
+    MissingFitness,           // Nicht alle Organismen wurden bewertet
+    MissingBehaviour,         // Bei Novelty Search hat nicht jeder Organismus ein Verhalten
+    MissingObjectives(usize), // Bei Multi-Objective hat nicht jeder Organismus so viele Ziele
+    NotFinite(&'static str),  // Eine Fitness, ein Verhalten oder ein Ziel ist NaN oder unendlich
+    InvalidState(String), // Eine Methode wurde zum falschen Zeitpunkt aufgerufen (z.B. finish_generation doppelt)
+    ThreadPool(Box<dyn Error + Send + Sync>),
+}
+
+impl NeatError {
+    pub fn invalid_config(field: &str, message: &str) -> Self {
+        NeatError::InvalidConfig(vec![ConfigIssue::new(field, message)])
+    }
+
+    pub fn io(path: PathBuf, source: io::Error) -> Self {
+        NeatError::Io { path, source }
+    }
+
+    pub fn serialization<E: Error + Send + Sync + 'static>(path: PathBuf, source: E) -> Self {
+        NeatError::Serialization {
+            path,
+            source: Box::new(source),
+        }
+    }
+}
+
+impl fmt::Display for NeatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NeatError::InvalidConfig(issues) => {
+                write!(f, "invalid config:")?;
+                issues
+                    .iter()
+                    .try_for_each(|issue| write!(f, "\n  {}", issue))
+            }
+            NeatError::Io { path, source } => {
+                write!(f, "error while accessing '{}': {}", path.display(), source)
+            }
+            NeatError::Serialization { path, source } => {
+                write!(
+                    f,
+                    "error while (de)serializing '{}': {}",
+                    path.display(),
+                    source
+                )
+            }
+            NeatError::UnknownFileExtension(path) => {
+                write!(f, "unknown file extension of '{}'", path.display())
+            }
+            NeatError::UnsupportedVersion {
+                path,
+                version: Some(version),
+            } => write!(
+                f,
+                "'{}' has format version {}, only versions up to {} are supported",
+                path.display(),
+                version,
+                FORMAT_VERSION
+            ),
+            NeatError::UnsupportedVersion {
+                path,
+                version: None,
+            } => write!(
+                f,
+                "'{}' has no format version and does not match any supported older format",
+                path.display()
+            ),
+            NeatError::InputLength { expected, actual } => {
+                write!(f, "expected {} inputs, got {}", expected, actual)
+            }
+            NeatError::OutputLength { expected, actual } => {
+                write!(f, "expected {} outputs, got {}", expected, actual)
+            }
+            NeatError::MissingFitness => write!(f, "not all organisms have been evaluated"),
+            NeatError::MissingBehaviour => write!(f, "not all organisms have a behaviour"),
+            NeatError::MissingObjectives(count) => {
+                write!(f, "not all organisms have {} objectives", count)
+            }
+            NeatError::NotFinite(value) => write!(f, "a {} is not a finite number", value),
+            NeatError::InvalidState(message) => write!(f, "{}", message),
+            NeatError::ThreadPool(source) => {
+                write!(f, "could not create the thread pool: {}", source)
+            }
+        }
+    }
+}
+
+impl Error for NeatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NeatError::Io { source, .. } => Some(source),
+            NeatError::Serialization { source, .. } | NeatError::ThreadPool(source) => {
+                Some(source.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
+
+// Ein Fehler oder eine Warnung zu einem Wert der Konfiguration. field ist der Pfad des Werts, z.B.
+// "reproduction.mutation_tiers[0].config.add_node_prob"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub field: String,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn new(field: &str, message: &str) -> Self {
+        ConfigIssue {
+            field: field.to_owned(),
+            message: message.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
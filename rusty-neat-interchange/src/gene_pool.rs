@@ -2,7 +2,10 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::io::{self, FileType};
+use crate::{
+    error::NeatError,
+    io::{self, FileType},
+};
 #[derive(Serialize, Deserialize)]
 pub struct PrintableGenePool {
     pub nodes: Vec<PrintableNode>,
@@ -22,6 +25,8 @@ pub struct PrintableConnection {
     pub innovation: u64,
     pub from: u64,
     pub to: u64,
+    #[serde(default)]
+    pub recurrent: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -35,10 +40,10 @@ pub fn write<T: Into<PrintableGenePool>>(
     pool: T,
     path: &Path,
     file_type: FileType,
-) -> Result<(), String> {
+) -> Result<(), NeatError> {
     io::write(path, pool.into(), file_type)
 }
 
-pub fn read<T: From<PrintableGenePool>>(path: &Path) -> Result<T, String> {
+pub fn read<T: From<PrintableGenePool>>(path: &Path) -> Result<T, NeatError> {
     io::read(path).map(|content: PrintableGenePool| content.into())
 }
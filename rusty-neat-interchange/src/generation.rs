@@ -1,28 +1,131 @@
 use std::path::Path;
 
-use serde::{Deserialize, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 
 use crate::{
+    error::NeatError,
     gene_pool::PrintableGenePool,
+    genome::PrintableActivation,
     io::{self, FileType},
+    migration::GenerationV0,
+    organism::PrintableOrganism,
     species::PrintableSpecies,
 };
 
+// Version des Formats von gen-N.*. Dateien ohne Versionsangabe stammen von Version 0 (siehe migration) oder
+// aus der Entwicklung von Version 1, in der die Felder mit serde(default) nach und nach hinzukamen.
+pub const FORMAT_VERSION: u32 = 1;
+const FORMAT_NAME: &str = "rusty-neat-generation";
+
+// Die Datei beginnt mit Name und Version des Formats, danach folgt die Generation
+#[derive(Serialize, Deserialize)]
+struct GenerationFile {
+    format: String,
+    format_version: u32,
+    content: PrintableGeneration,
+}
+
+#[derive(Deserialize)]
+struct FileHeader {
+    format: String,
+    format_version: u32,
+}
+
+// Werte, die in Dateien der Version 0 fehlen: Aktivierungsfunktion und Bias aller Nodes außer den Inputs
+// (entspricht evaluation.activation und evaluation.bias der damaligen Konfiguration)
+pub struct MigrationDefaults {
+    pub activation: PrintableActivation,
+    pub bias: f64,
+}
+
+impl Default for MigrationDefaults {
+    fn default() -> Self {
+        MigrationDefaults {
+            activation: PrintableActivation::Sigmoid,
+            bias: 0.0,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PrintableGeneration {
     pub generation: u32,
     pub species: Vec<PrintableSpecies>,
     pub pool: PrintableGenePool,
+    #[serde(default)]
+    pub organisms: Vec<PrintableOrganism>, // Alle Organismen, auch die aus entfernten Spezies
+    #[serde(default)]
+    pub next_species_id: usize,
+    #[serde(default)]
+    pub last_genome_id: u64,
+    #[serde(default)]
+    pub distance_tolerance: Option<f64>,
+    #[serde(default)]
+    pub phase: PrintablePhase,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PrintablePhase {
+    pub simplifying: bool,
+    pub complexity_floor: Option<f64>,
+    pub lowest_complexity: f64,
+    pub last_decrease: u32,
 }
 
 pub fn write<T: Into<PrintableGeneration>>(
     generation: T,
     path: &Path,
     file_type: FileType,
-) -> Result<(), String> {
-    io::write(path, generation.into(), file_type)
+) -> Result<(), NeatError> {
+    let file = GenerationFile {
+        format: FORMAT_NAME.to_owned(),
+        format_version: FORMAT_VERSION,
+        content: generation.into(),
+    };
+    io::write(path, file, file_type)
 }
 
-pub fn read<T: From<PrintableGeneration>>(path: &Path) -> Result<T, String> {
-    io::read(path).map(|content: PrintableGeneration| content.into())
+pub fn read<T: From<PrintableGeneration>>(path: &Path) -> Result<T, NeatError> {
+    read_migrated(path, &MigrationDefaults::default())
+}
+
+// Liest eine Generation beliebiger Version, ältere Versionen werden mit defaults in das aktuelle Format überführt
+pub fn read_migrated<T: From<PrintableGeneration>>(
+    path: &Path,
+    defaults: &MigrationDefaults,
+) -> Result<T, NeatError> {
+    let (file_content, file_type) = io::read_bytes(path)?;
+    let header: Option<FileHeader> = io::deserialize_prefix(&file_content, file_type);
+    let generation = match header {
+        Some(header) if header.format == FORMAT_NAME => {
+            if header.format_version != FORMAT_VERSION {
+                return Err(NeatError::UnsupportedVersion {
+                    path: path.to_owned(),
+                    version: Some(header.format_version),
+                });
+            }
+            io::deserialize::<GenerationFile>(path, &file_content, file_type)?.content
+        }
+        _ => {
+            if let Ok(old) = io::deserialize::<GenerationV0>(path, &file_content, file_type) {
+                old.migrate(defaults)
+            } else {
+                io::deserialize(path, &file_content, file_type).map_err(|err| {
+                    // Ungültiges JSON stammt nicht von einer älteren Version, sondern ist beschädigt
+                    let json = matches!(file_type, FileType::PrettyJSON | FileType::CompactJSON);
+                    if json
+                        && io::deserialize_prefix::<IgnoredAny>(&file_content, file_type).is_none()
+                    {
+                        err
+                    } else {
+                        NeatError::UnsupportedVersion {
+                            path: path.to_owned(),
+                            version: None,
+                        }
+                    }
+                })?
+            }
+        }
+    };
+    Ok(generation.into())
 }
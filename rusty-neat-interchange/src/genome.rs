@@ -2,15 +2,42 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::io::{self, FileType};
+use crate::{
+    error::NeatError,
+    io::{self, FileType},
+};
 #[derive(Serialize, Deserialize)]
 pub struct PrintableGenome {
     pub connections: Vec<PrintableConnectionGene>,
-    pub nodes: Vec<u64>,
+    pub nodes: Vec<PrintableNodeGene>,
     pub id: u64,
     pub generation: u32,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct PrintableNodeGene {
+    pub id: u64,
+    pub activation: PrintableActivation,
+    pub bias: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum PrintableActivation {
+    Identity,
+    Sigmoid,
+    Relu,
+    Tanh,
+    Gaussian,
+    Sin,
+    Abs,
+    Step,
+    Softplus,
+    Clamped,
+    ClampedRelu,
+    Cos,
+    Square,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PrintableConnectionGene {
     pub innovation: u64,
@@ -22,10 +49,10 @@ pub fn write<T: Into<PrintableGenome>>(
     genome: T,
     path: &Path,
     file_type: FileType,
-) -> Result<(), String> {
+) -> Result<(), NeatError> {
     io::write(path, genome.into(), file_type)
 }
 
-pub fn read<T: From<PrintableGenome>>(path: &Path) -> Result<T, String> {
+pub fn read<T: From<PrintableGenome>>(path: &Path) -> Result<T, NeatError> {
     io::read(path).map(|content: PrintableGenome| content.into())
 }
@@ -1,7 +1,10 @@
-use std::{error::Error, fs, path::Path};
+use std::{fs, path::Path};
 
+use bincode::Options;
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::error::NeatError;
+
 #[derive(Clone, Copy)]
 pub enum FileType {
     PrettyJSON,
@@ -18,58 +21,80 @@ impl FileType {
         }
     }
 
-    pub fn from_ext(ext: &str) -> Result<Self, &str> {
+    pub fn from_ext(ext: &str) -> Option<Self> {
         match ext {
-            "json" => Ok(FileType::PrettyJSON),
-            "cjson" => Ok(FileType::CompactJSON),
-            "bin" => Ok(FileType::Bincode),
-            _ => Err("Unknown file extension"),
+            "json" => Some(FileType::PrettyJSON),
+            "cjson" => Some(FileType::CompactJSON),
+            "bin" => Some(FileType::Bincode),
+            _ => None,
         }
     }
+
+    // Der Dateityp anhand der Endung des Pfads
+    pub fn from_path(path: &Path) -> Result<Self, NeatError> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(FileType::from_ext)
+            .ok_or_else(|| NeatError::UnknownFileExtension(path.to_owned()))
+    }
 }
 
 pub(crate) fn write<T: Serialize>(
     path: &Path,
     content: T,
     file_type: FileType,
-) -> Result<(), String> {
+) -> Result<(), NeatError> {
     let file_content = match file_type {
-        FileType::PrettyJSON => serde_json::to_string_pretty(&content)
-            .map_err(|e| stringify(e, path))?
-            .as_bytes()
-            .to_vec(),
-        FileType::CompactJSON => serde_json::to_string(&content)
-            .map_err(|e| stringify(e, path))?
-            .as_bytes()
-            .to_vec(),
-        FileType::Bincode => bincode::serialize(&content).map_err(|e| stringify(e, path))?,
+        FileType::PrettyJSON => serde_json::to_vec_pretty(&content)
+            .map_err(|e| NeatError::serialization(path.to_owned(), e))?,
+        FileType::CompactJSON => serde_json::to_vec(&content)
+            .map_err(|e| NeatError::serialization(path.to_owned(), e))?,
+        FileType::Bincode => bincode::serialize(&content)
+            .map_err(|e| NeatError::serialization(path.to_owned(), e))?,
     };
-    Ok(fs::write(path, file_content).map_err(|e| stringify(e, path))?)
+    fs::write(path, file_content).map_err(|e| NeatError::io(path.to_owned(), e))
 }
 
-pub(crate) fn read<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
-    if path.is_dir() {
-        return Err("path is an directory".to_string());
-    }
+pub(crate) fn read<T: DeserializeOwned>(path: &Path) -> Result<T, NeatError> {
+    let (file_content, file_type) = read_bytes(path)?;
+    deserialize(path, &file_content, file_type)
+}
 
-    let file_content = fs::read(path).map_err(|e| stringify(e, path))?;
-    match FileType::from_ext(
-        path.extension()
-            .expect("file has no extension")
-            .to_str()
-            .expect("Invalid extension: Could not be converted to a rust string"),
-    )? {
-        FileType::PrettyJSON | FileType::CompactJSON => {
-            serde_json::from_slice(&file_content).map_err(|e| stringify(e, path))
-        }
-        FileType::Bincode => bincode::deserialize(&file_content).map_err(|e| stringify(e, path)),
+pub(crate) fn read_bytes(path: &Path) -> Result<(Vec<u8>, FileType), NeatError> {
+    let file_type = FileType::from_path(path)?;
+    let file_content = fs::read(path).map_err(|e| NeatError::io(path.to_owned(), e))?;
+    Ok((file_content, file_type))
+}
+
+// Bincode beschreibt die Felder nicht, deshalb darf nach dem Inhalt nichts übrig bleiben. So schlägt das
+// Lesen einer Datei mit einem anderen Aufbau (fast immer) fehl.
+pub(crate) fn deserialize<T: DeserializeOwned>(
+    path: &Path,
+    file_content: &[u8],
+    file_type: FileType,
+) -> Result<T, NeatError> {
+    match file_type {
+        FileType::PrettyJSON | FileType::CompactJSON => serde_json::from_slice(file_content)
+            .map_err(|e| NeatError::serialization(path.to_owned(), e)),
+        FileType::Bincode => bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize(file_content)
+            .map_err(|e| NeatError::serialization(path.to_owned(), e)),
     }
 }
 
-fn stringify<E: Error>(err: E, file: &Path) -> String {
-    format!(
-        "Error while reading file '{}': {}",
-        file.to_str().unwrap_or("<path not printable>"),
-        err.to_string()
-    )
+// Liest nur den Anfang der Datei, z.B. eine Versionsangabe vor dem eigentlichen Inhalt
+pub(crate) fn deserialize_prefix<T: DeserializeOwned>(
+    file_content: &[u8],
+    file_type: FileType,
+) -> Option<T> {
+    match file_type {
+        FileType::PrettyJSON | FileType::CompactJSON => serde_json::from_slice(file_content).ok(),
+        FileType::Bincode => bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(1024)
+            .allow_trailing_bytes()
+            .deserialize(file_content)
+            .ok(),
+    }
 }
@@ -1,7 +1,10 @@
+pub mod error;
 pub mod gene_pool;
 pub mod generation;
 pub mod genome;
 pub mod io;
+mod migration;
 pub mod neat_result;
+pub mod novelty;
 pub mod organism;
 pub mod species;
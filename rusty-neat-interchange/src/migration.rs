@@ -0,0 +1,156 @@
+use serde::Deserialize;
+
+use crate::{
+    gene_pool::{PrintableConnection, PrintableGenePool, PrintableNode, PrintableNodeType},
+    generation::{MigrationDefaults, PrintableGeneration, PrintablePhase},
+    genome::{PrintableActivation, PrintableConnectionGene, PrintableGenome, PrintableNodeGene},
+    organism::PrintableOrganism,
+    species::PrintableSpecies,
+};
+
+// Aufbau der Generationen vor der Einführung der Formatversion (Version 0). Die Nodes eines Genomes waren nur
+// Ids, alle Nodes außer den Inputs verwendeten die Aktivierungsfunktion und den Bias aus der EvaluationConfig.
+#[derive(Deserialize)]
+pub(crate) struct GenerationV0 {
+    generation: u32,
+    species: Vec<SpeciesV0>,
+    pool: GenePoolV0,
+}
+
+#[derive(Deserialize)]
+struct SpeciesV0 {
+    representative: OrganismV0,
+    organisms: Vec<OrganismV0>,
+    fitness: Option<f64>,
+    id: usize,
+}
+
+#[derive(Deserialize)]
+struct OrganismV0 {
+    genome: GenomeV0,
+    fitness: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct GenomeV0 {
+    connections: Vec<PrintableConnectionGene>,
+    nodes: Vec<u64>,
+    id: u64,
+    generation: u32,
+}
+
+#[derive(Deserialize)]
+struct GenePoolV0 {
+    nodes: Vec<PrintableNode>,
+    connections: Vec<ConnectionV0>,
+}
+
+#[derive(Deserialize)]
+struct ConnectionV0 {
+    innovation: u64,
+    from: u64,
+    to: u64,
+}
+
+impl GenerationV0 {
+    // Fehlende Werte erhalten Standardwerte, die Population ergänzt beim Fortsetzen die Ids und die Toleranz
+    pub(crate) fn migrate(self, defaults: &MigrationDefaults) -> PrintableGeneration {
+        let generation = self.generation;
+        let inputs: Vec<u64> = self
+            .pool
+            .nodes
+            .iter()
+            .filter(|node| matches!(node.node_type, PrintableNodeType::Input(_)))
+            .map(|node| node.id)
+            .collect();
+        PrintableGeneration {
+            generation,
+            species: self
+                .species
+                .into_iter()
+                .map(|species| species.migrate(generation, &inputs, defaults))
+                .collect(),
+            pool: PrintableGenePool {
+                nodes: self.pool.nodes,
+                connections: self
+                    .pool
+                    .connections
+                    .into_iter()
+                    .map(|connection| PrintableConnection {
+                        innovation: connection.innovation,
+                        from: connection.from,
+                        to: connection.to,
+                        recurrent: false, // Version 0 erlaubte keine Rückwärtskanten
+                    })
+                    .collect(),
+            },
+            organisms: Vec::new(), // Die Organismen werden aus den Spezies übernommen
+            next_species_id: 0,
+            last_genome_id: 0,
+            distance_tolerance: None,
+            phase: PrintablePhase::default(),
+        }
+    }
+}
+
+impl SpeciesV0 {
+    fn migrate(
+        self,
+        generation: u32,
+        inputs: &[u64],
+        defaults: &MigrationDefaults,
+    ) -> PrintableSpecies {
+        PrintableSpecies {
+            representative: self.representative.migrate(inputs, defaults),
+            organisms: self
+                .organisms
+                .into_iter()
+                .map(|organism| organism.migrate(inputs, defaults))
+                .collect(),
+            fitness: self.fitness,
+            id: self.id,
+            best_fitness: None,
+            // Ohne Verlauf gilt jede Spezies als gerade verbessert, damit sie nicht sofort als stagnierend entfernt wird
+            last_improvement: generation,
+            created: 0,
+        }
+    }
+}
+
+impl OrganismV0 {
+    fn migrate(self, inputs: &[u64], defaults: &MigrationDefaults) -> PrintableOrganism {
+        PrintableOrganism {
+            genome: PrintableGenome {
+                connections: self.genome.connections,
+                nodes: self
+                    .genome
+                    .nodes
+                    .into_iter()
+                    .map(|id| {
+                        // Inputs geben ihren Wert unverändert weiter (wie bei Genome::new)
+                        if inputs.contains(&id) {
+                            PrintableNodeGene {
+                                id,
+                                activation: PrintableActivation::Identity,
+                                bias: 0.0,
+                            }
+                        } else {
+                            PrintableNodeGene {
+                                id,
+                                activation: defaults.activation,
+                                bias: defaults.bias,
+                            }
+                        }
+                    })
+                    .collect(),
+                id: self.genome.id,
+                generation: self.genome.generation,
+            },
+            fitness: self.fitness,
+            behaviour: None,
+            novelty: None,
+            objectives: None,
+            pareto_rank: None,
+        }
+    }
+}
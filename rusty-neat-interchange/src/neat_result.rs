@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use crate::{
+    error::NeatError,
     gene_pool::PrintableGenePool,
     genome::PrintableGenome,
     io::{self, FileType},
@@ -18,10 +19,10 @@ pub fn write<T: Into<PrintableNeatResult>>(
     result: T,
     path: &Path,
     file_type: FileType,
-) -> Result<(), String> {
+) -> Result<(), NeatError> {
     io::write(path, result.into(), file_type)
 }
 
-pub fn read<T: From<PrintableNeatResult>>(path: &Path) -> Result<T, String> {
+pub fn read<T: From<PrintableNeatResult>>(path: &Path) -> Result<T, NeatError> {
     io::read(path).map(|content: PrintableNeatResult| content.into())
 }
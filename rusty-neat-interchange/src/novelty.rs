@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::NeatError,
+    io::{self, FileType},
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct PrintableNoveltyArchive {
+    pub behaviours: Vec<Vec<f64>>,
+}
+
+pub fn write<T: Into<PrintableNoveltyArchive>>(
+    archive: T,
+    path: &Path,
+    file_type: FileType,
+) -> Result<(), NeatError> {
+    io::write(path, archive.into(), file_type)
+}
+
+pub fn read<T: From<PrintableNoveltyArchive>>(path: &Path) -> Result<T, NeatError> {
+    io::read(path).map(|content: PrintableNoveltyArchive| content.into())
+}
@@ -3,6 +3,7 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    error::NeatError,
     genome::PrintableGenome,
     io::{self, FileType},
 };
@@ -10,16 +11,20 @@ use crate::{
 pub struct PrintableOrganism {
     pub genome: PrintableGenome,
     pub fitness: Option<f64>,
+    pub behaviour: Option<Vec<f64>>,
+    pub novelty: Option<f64>,
+    pub objectives: Option<Vec<f64>>,
+    pub pareto_rank: Option<usize>,
 }
 
 pub fn write<T: Into<PrintableOrganism>>(
     organism: T,
     path: &Path,
     file_type: FileType,
-) -> Result<(), String> {
+) -> Result<(), NeatError> {
     io::write(path, organism.into(), file_type)
 }
 
-pub fn read<T: From<PrintableOrganism>>(path: &Path) -> Result<T, String> {
+pub fn read<T: From<PrintableOrganism>>(path: &Path) -> Result<T, NeatError> {
     io::read(path).map(|content: PrintableOrganism| content.into())
 }
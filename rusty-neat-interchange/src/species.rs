@@ -3,6 +3,7 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    error::NeatError,
     io::{self, FileType},
     organism::PrintableOrganism,
 };
@@ -13,16 +14,21 @@ pub struct PrintableSpecies {
     pub organisms: Vec<PrintableOrganism>,
     pub fitness: Option<f64>,
     pub id: usize,
+    pub best_fitness: Option<f64>,
+    #[serde(default)]
+    pub last_improvement: u32,
+    #[serde(default)]
+    pub created: u32,
 }
 
 pub fn write<T: Into<PrintableSpecies>>(
     species: T,
     path: &Path,
     file_type: FileType,
-) -> Result<(), String> {
+) -> Result<(), NeatError> {
     io::write(path, species.into(), file_type)
 }
 
-pub fn read<T: From<PrintableSpecies>>(path: &Path) -> Result<T, String> {
+pub fn read<T: From<PrintableSpecies>>(path: &Path) -> Result<T, NeatError> {
     io::read(path).map(|content: PrintableSpecies| content.into())
 }